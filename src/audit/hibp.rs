@@ -0,0 +1,198 @@
+//! Have I Been Pwned password range lookups
+//!
+//! Uses the k-anonymity range API: the password is hashed with SHA-1 and only
+//! the first five hex characters of the hash are sent. The service replies
+//! with every known suffix under that prefix and the match happens locally,
+//! so neither the password nor its full hash ever leaves the machine.
+
+use super::AuditError;
+use crate::vault::Vault;
+use data_encoding::HEXUPPER;
+use sha1::{Digest, Sha1};
+
+/// Number of hash characters sent to the service
+const PREFIX_LEN: usize = 5;
+
+/// Fetches the suffix list for a hash prefix
+pub trait RangeClient {
+    /// Returns the raw `SUFFIX:COUNT` response body for a 5 character prefix
+    fn fetch_range(&self, prefix: &str) -> Result<String, AuditError>;
+}
+
+/// Range client backed by api.pwnedpasswords.com
+#[cfg(feature = "hibp")]
+pub struct HttpRangeClient {
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "hibp")]
+impl HttpRangeClient {
+    const BASE_URL: &'static str = "https://api.pwnedpasswords.com/range/";
+
+    pub fn new() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(std::time::Duration::from_secs(10))
+                .user_agent(concat!("kookie/", env!("CARGO_PKG_VERSION")))
+                .build(),
+        }
+    }
+}
+
+#[cfg(feature = "hibp")]
+impl Default for HttpRangeClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "hibp")]
+impl RangeClient for HttpRangeClient {
+    fn fetch_range(&self, prefix: &str) -> Result<String, AuditError> {
+        // Padding hides the real number of matches from anyone watching the response size
+        self.agent
+            .get(&format!("{}{}", Self::BASE_URL, prefix))
+            .set("Add-Padding", "true")
+            .call()
+            .map_err(|e| AuditError::RequestFailed(e.to_string()))?
+            .into_string()
+            .map_err(|e| AuditError::RequestFailed(e.to_string()))
+    }
+}
+
+/// Returns how many times the password appears in known breaches (0 if never)
+#[cfg(feature = "hibp")]
+pub fn check_password_pwned(password: &str) -> Result<u64, AuditError> {
+    check_password_pwned_with(&HttpRangeClient::new(), password)
+}
+
+/// Like `check_password_pwned` but with a caller-supplied client
+pub fn check_password_pwned_with(
+    client: &impl RangeClient,
+    password: &str,
+) -> Result<u64, AuditError> {
+    let hash = HEXUPPER.encode(&Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = hash.split_at(PREFIX_LEN);
+
+    let body = client.fetch_range(prefix)?;
+    for line in body.lines() {
+        let (candidate, count) = line
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| AuditError::InvalidResponse(line.to_string()))?;
+        if candidate.eq_ignore_ascii_case(suffix) {
+            return count
+                .trim()
+                .parse()
+                .map_err(|_| AuditError::InvalidResponse(line.to_string()));
+        }
+    }
+
+    Ok(0)
+}
+
+impl Vault {
+    /// Returns (name, breach count) for every stored password found in a breach
+    #[cfg(feature = "hibp")]
+    pub fn audit_passwords(&self) -> Result<Vec<(String, u64)>, AuditError> {
+        self.audit_passwords_with(&HttpRangeClient::new())
+    }
+
+    /// Like `audit_passwords` but with a caller-supplied client
+    pub fn audit_passwords_with(
+        &self,
+        client: &impl RangeClient,
+    ) -> Result<Vec<(String, u64)>, AuditError> {
+        let mut breached = Vec::new();
+        for p in self.list_passwords() {
+            let count = check_password_pwned_with(client, p.password.expose_secret())?;
+            if count > 0 {
+                breached.push((p.name.clone(), count));
+            }
+        }
+        Ok(breached)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use crate::vault::types::Password;
+    use std::cell::RefCell;
+
+    /// Serves canned responses and records every prefix it was asked for
+    struct MockClient {
+        responses: Vec<(&'static str, &'static str)>,
+        requested: RefCell<Vec<String>>,
+    }
+
+    impl MockClient {
+        fn new(responses: Vec<(&'static str, &'static str)>) -> Self {
+            Self {
+                responses,
+                requested: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl RangeClient for MockClient {
+        fn fetch_range(&self, prefix: &str) -> Result<String, AuditError> {
+            self.requested.borrow_mut().push(prefix.to_string());
+            Ok(self
+                .responses
+                .iter()
+                .find(|(p, _)| *p == prefix)
+                .map(|(_, body)| body.to_string())
+                .unwrap_or_default())
+        }
+    }
+
+    // SHA-1("password") = 5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8
+    const PASSWORD_RANGE: &str = "1D2DA4053E34E76F6576ED1DA63134B5E2A:2\r\n\
+         1E4C9B93F3F0682250B6CF8331B7EE68FD8:9659365\r\n\
+         1F2B668E8AABEF1C59E9EC6F82E3F3CD786:0\r\n";
+
+    #[test]
+    fn test_matches_suffix_and_sends_only_prefix() {
+        let client = MockClient::new(vec![("5BAA6", PASSWORD_RANGE)]);
+
+        assert_eq!(
+            check_password_pwned_with(&client, "password").unwrap(),
+            9659365
+        );
+        assert_eq!(*client.requested.borrow(), vec!["5BAA6".to_string()]);
+    }
+
+    #[test]
+    fn test_unknown_password_is_zero() {
+        let client = MockClient::new(vec![("5BAA6", PASSWORD_RANGE)]);
+        assert_eq!(
+            check_password_pwned_with(&client, "a-long-unique-pw").unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_malformed_response_is_an_error() {
+        let client = MockClient::new(vec![("5BAA6", "not a range response")]);
+        assert!(matches!(
+            check_password_pwned_with(&client, "password"),
+            Err(AuditError::InvalidResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_audit_passwords_reports_breached_names() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        vault.data.passwords = vec![
+            Password::new("old".into(), "password".into(), None, None, None),
+            Password::new("new".into(), "a-long-unique-pw".into(), None, None, None),
+        ];
+        let client = MockClient::new(vec![("5BAA6", PASSWORD_RANGE)]);
+
+        let breached = vault.audit_passwords_with(&client).unwrap();
+        assert_eq!(breached, vec![("old".to_string(), 9659365)]);
+        assert!(client.requested.borrow().iter().all(|p| p.len() == 5));
+    }
+}
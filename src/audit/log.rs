@@ -0,0 +1,197 @@
+//! Append-only log of vault operations
+//!
+//! Each line of the log is one JSON `AuditEntry`: what happened, when, and
+//! which secret it concerned, identified by type and name only. Secret values
+//! are never written; reveals record the reason given instead.
+//!
+//! The log lives next to the vault file (`vault.json.audit.log`) and is
+//! enabled with `Vault::with_audit`.
+
+use super::AuditError;
+use crate::vault::types::SecretType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Kind of operation recorded in the audit log
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEvent {
+    Unlock,
+    UnlockFailed,
+    Add,
+    Update,
+    Delete,
+    Export,
+    Import,
+    Reveal,
+}
+
+/// One line of the audit log
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub event: AuditEvent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_type: Option<SecretType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Why the secret was revealed, for `Reveal` entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Appends entries to an audit log file
+#[derive(Debug, Clone)]
+pub struct AuditLogger {
+    path: PathBuf,
+}
+
+impl AuditLogger {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Logger writing to the sidecar file of a vault (`<vault file>.audit.log`)
+    pub fn for_vault(vault_path: &Path) -> Self {
+        let mut name = vault_path.file_name().unwrap_or_default().to_os_string();
+        name.push(".audit.log");
+        Self::new(vault_path.with_file_name(name))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends one entry, creating the log (owner-only on Unix) if needed
+    pub fn log(
+        &self,
+        event: AuditEvent,
+        secret: Option<(SecretType, &str)>,
+    ) -> Result<(), AuditError> {
+        self.append(AuditEntry {
+            timestamp: Utc::now(),
+            event,
+            secret_type: secret.map(|(secret_type, _)| secret_type),
+            name: secret.map(|(_, name)| name.to_string()),
+            reason: None,
+        })
+    }
+
+    /// Appends a `Reveal` entry for `secret` with the reason it was revealed
+    pub fn log_reveal(&self, secret: (SecretType, &str), reason: &str) -> Result<(), AuditError> {
+        self.append(AuditEntry {
+            timestamp: Utc::now(),
+            event: AuditEvent::Reveal,
+            secret_type: Some(secret.0),
+            name: Some(secret.1.to_string()),
+            reason: Some(reason.to_string()),
+        })
+    }
+
+    fn append(&self, entry: AuditEntry) -> Result<(), AuditError> {
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(&self.path)?.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Reads every entry of an audit log, oldest first
+pub fn read_audit_log(path: &Path) -> Result<Vec<AuditEntry>, AuditError> {
+    fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| AuditError::InvalidLogLine(idx + 1, e.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use crate::vault::types::Password;
+    use crate::vault::Vault;
+
+    #[test]
+    fn test_add_and_delete_are_logged_without_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+        let logger = AuditLogger::for_vault(&path);
+        let mut vault = Vault::with_path(path).with_audit(logger.clone());
+        vault.init("pw").unwrap();
+
+        vault
+            .add_password(Password::new(
+                "github".into(),
+                "hunter2-value".into(),
+                None,
+                None,
+                None,
+            ))
+            .unwrap();
+        vault.delete_password("github").unwrap();
+
+        let entries = read_audit_log(logger.path()).unwrap();
+        let events: Vec<_> = entries.iter().map(|e| e.event).collect();
+        assert_eq!(events, vec![AuditEvent::Add, AuditEvent::Delete]);
+        assert!(entries
+            .iter()
+            .all(|e| e.secret_type == Some(SecretType::Password)
+                && e.name.as_deref() == Some("github")));
+
+        let raw = fs::read_to_string(logger.path()).unwrap();
+        assert!(!raw.contains("hunter2"));
+        assert!(raw.contains(r#""secret_type":"password""#));
+    }
+
+    #[test]
+    fn test_unlock_attempts_are_logged() {
+        let (_dir, vault) = test_util::test_vault();
+
+        let logger = AuditLogger::for_vault(&vault.path);
+        let mut vault = Vault::with_path(vault.path.clone()).with_audit(logger.clone());
+        vault.unlock("wrong").unwrap_err();
+        vault.unlock("pw").unwrap();
+
+        let events: Vec<_> = read_audit_log(logger.path())
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.event, e.name))
+            .collect();
+        assert_eq!(
+            events,
+            vec![(AuditEvent::UnlockFailed, None), (AuditEvent::Unlock, None)]
+        );
+    }
+
+    #[test]
+    fn test_read_rejects_corrupt_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        fs::write(
+            &path,
+            "{\"timestamp\":\"2024-01-01T00:00:00Z\",\"event\":\"export\"}\nnot json\n",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            read_audit_log(&path),
+            Err(AuditError::InvalidLogLine(2, _))
+        ));
+    }
+}
@@ -0,0 +1,24 @@
+//! Password auditing against external breach data, and the vault's audit log
+//!
+//! Network access is only compiled in with the `hibp` feature. The lookup
+//! logic itself is feature-independent so it can be tested with a mock client.
+
+pub mod hibp;
+pub mod log;
+
+use thiserror::Error;
+
+/// Audit errors
+#[derive(Error, Debug)]
+pub enum AuditError {
+    #[error("Breach lookup failed: {0}")]
+    RequestFailed(String),
+    #[error("Invalid breach API response: {0}")]
+    InvalidResponse(String),
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    #[error("Invalid audit log entry on line {0}: {1}")]
+    InvalidLogLine(usize, String),
+}
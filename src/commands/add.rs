@@ -1,9 +1,13 @@
 //! Add secrets command
 
 use crate::commands::lock::ensure_unlocked;
+use crate::config::Config;
 use crate::session::cache;
-use crate::utils::{display, input};
+use crate::utils::generators::PasswordOptions;
+use crate::utils::{display, input, strength};
 use crate::vault::types::*;
+use colored::*;
+use std::path::{Path, PathBuf};
 
 /// Secret type to add
 #[derive(Debug, Clone, Copy)]
@@ -13,24 +17,105 @@ pub enum AddType {
     Note,
     DbCredential,
     Token,
+    Totp,
+    SshKey,
+    CreditCard,
+    Wifi,
+    Template(Template),
+}
+
+/// Preset service for `kookie add --template`
+#[derive(Debug, Clone, Copy)]
+pub enum Template {
+    Stripe,
+    OpenAi,
+    GithubPat,
+    PostgresLocal,
+    MysqlLocal,
 }
 
 /// Runs the add command
-pub fn run(secret_type: AddType) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// With `generate`, passwords and API keys are generated instead of prompted for.
+pub fn run(secret_type: AddType, generate: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if generate && !matches!(secret_type, AddType::Password | AddType::ApiKey) {
+        display::error("--generate only works with --password and --api-key.");
+        return Ok(());
+    }
+    
     let mut vault = ensure_unlocked()?;
     
     match secret_type {
-        AddType::Password => add_password(&mut vault)?,
-        AddType::ApiKey => add_api_key(&mut vault)?,
+        AddType::Password => add_password(&mut vault, generate)?,
+        AddType::ApiKey => add_api_key(&mut vault, generate)?,
         AddType::Note => add_note(&mut vault)?,
         AddType::DbCredential => add_db_credential(&mut vault)?,
         AddType::Token => add_token(&mut vault)?,
+        AddType::Totp => add_totp(&mut vault)?,
+        AddType::SshKey => add_ssh_key(&mut vault)?,
+        AddType::CreditCard => add_credit_card(&mut vault)?,
+        AddType::Wifi => add_wifi(&mut vault)?,
+        AddType::Template(template) => add_from_template(&mut vault, template)?,
     }
     
     Ok(())
 }
 
-fn add_password(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error::Error>> {
+/// Adds a secret from a template, prompting only for the values it can't preset
+fn add_from_template(vault: &mut crate::vault::Vault, template: Template) -> Result<(), Box<dyn std::error::Error>> {
+    println!();
+    
+    let name = match template {
+        Template::Stripe | Template::OpenAi => {
+            let key = input::prompt_password("API Key:")?;
+            if key.is_empty() {
+                display::error("API key is required.");
+                return Ok(());
+            }
+            let secret = match template {
+                Template::Stripe => ApiKey::for_stripe(key),
+                _ => ApiKey::for_openai(key),
+            };
+            let name = secret.name.clone();
+            vault.add_api_key(secret)?;
+            name
+        }
+        Template::GithubPat => {
+            let token = input::prompt_password("Token:")?;
+            if token.is_empty() {
+                display::error("Token is required.");
+                return Ok(());
+            }
+            let secret = Token::github_pat(token);
+            let name = secret.name.clone();
+            vault.add_token(secret)?;
+            name
+        }
+        Template::PostgresLocal | Template::MysqlLocal => {
+            let database = input::prompt_text("Database name:")?;
+            if database.is_empty() {
+                display::error("Database name is required.");
+                return Ok(());
+            }
+            let username = input::prompt_text("Username:")?;
+            let password = input::prompt_password("Password:")?;
+            let secret = match template {
+                Template::PostgresLocal => DbCredential::postgres_local(database, username, password),
+                _ => DbCredential::mysql_local(database, username, password),
+            };
+            let name = secret.name.clone();
+            vault.add_db_credential(secret)?;
+            name
+        }
+    };
+    
+    refresh_session(&vault.path)?;
+    
+    display::success(&format!("'{}' added from template!", name));
+    Ok(())
+}
+
+fn add_password(vault: &mut crate::vault::Vault, generate: bool) -> Result<(), Box<dyn std::error::Error>> {
     println!();
     display::info("Adding new password...");
     println!();
@@ -45,23 +130,42 @@ fn add_password(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::erro
     let username = input::prompt_optional("Username (optional):")?;
     let url = input::prompt_optional("URL (optional):")?;
     
+    if generate {
+        let opts = PasswordOptions {
+            include_symbols: true,
+            require_each_class: true,
+            ..Config::current().password_options()
+        };
+        let created = vault.add_generated_password(name.clone(), &opts, description, username, url)?;
+        refresh_session(&vault.path)?;
+        
+        display::success(&format!("Password '{}' generated and added!", name));
+        display::print_secret("Password", created.password.expose_secret(), true);
+        return Ok(());
+    }
+    
     let password = input::prompt_password("Password:")?;
     if password.is_empty() {
         display::error("Password is required.");
         return Ok(());
     }
     
+    if !confirm_strength(&password)? {
+        display::info("Aborted.");
+        return Ok(());
+    }
+    
     let secret = Password::new(name.clone(), password, description, username, url);
     vault.add_password(secret)?;
     
     // Refresh session
-    refresh_session()?;
+    refresh_session(&vault.path)?;
     
     display::success(&format!("Password '{}' added successfully!", name));
     Ok(())
 }
 
-fn add_api_key(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error::Error>> {
+fn add_api_key(vault: &mut crate::vault::Vault, generate: bool) -> Result<(), Box<dyn std::error::Error>> {
     println!();
     display::info("Adding new API key...");
     println!();
@@ -75,6 +179,15 @@ fn add_api_key(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error
     let description = input::prompt_optional("Description (optional):")?;
     let service = input::prompt_optional("Service (optional, e.g., 'Stripe'):")?;
     
+    if generate {
+        let created = vault.add_generated_api_key(name.clone(), description, service)?;
+        refresh_session(&vault.path)?;
+        
+        display::success(&format!("API key '{}' generated and added!", name));
+        display::print_secret("API Key", created.key.expose_secret(), true);
+        return Ok(());
+    }
+    
     let key = input::prompt_password("API Key:")?;
     if key.is_empty() {
         display::error("API key is required.");
@@ -84,7 +197,7 @@ fn add_api_key(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error
     let secret = ApiKey::new(name.clone(), key, description, service);
     vault.add_api_key(secret)?;
     
-    refresh_session()?;
+    refresh_session(&vault.path)?;
     
     display::success(&format!("API key '{}' added successfully!", name));
     Ok(())
@@ -101,6 +214,8 @@ fn add_note(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error::E
         return Ok(());
     }
     
+    let description = input::prompt_optional("Description (optional):")?;
+    
     println!("Content (end with an empty line):");
     let mut content = String::new();
     loop {
@@ -117,10 +232,10 @@ fn add_note(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error::E
         return Ok(());
     }
     
-    let secret = Note::new(name.clone(), content.trim().to_string());
+    let secret = Note::new(name.clone(), content.trim().to_string()).with_description(description);
     vault.add_note(secret)?;
     
-    refresh_session()?;
+    refresh_session(&vault.path)?;
     
     display::success(&format!("Note '{}' added successfully!", name));
     Ok(())
@@ -138,7 +253,7 @@ fn add_db_credential(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std:
     }
     
     let description = input::prompt_optional("Description (optional):")?;
-    let db_type = input::prompt_optional("Database type (postgres/mysql/mongodb):")?;
+    let db_type = input::prompt_optional("Database type (postgres/mysql/mariadb/mongodb/mssql/redis):")?;
     
     let host = input::prompt_text("Host:")?;
     if host.is_empty() {
@@ -167,6 +282,11 @@ fn add_db_credential(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std:
         return Ok(());
     }
     
+    if !confirm_strength(&password)? {
+        display::info("Aborted.");
+        return Ok(());
+    }
+    
     let secret = DbCredential::new(
         name.clone(),
         host,
@@ -179,7 +299,7 @@ fn add_db_credential(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std:
     );
     vault.add_db_credential(secret)?;
     
-    refresh_session()?;
+    refresh_session(&vault.path)?;
     
     display::success(&format!("Database credential '{}' added successfully!", name));
     Ok(())
@@ -211,17 +331,186 @@ fn add_token(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error::
     let secret = Token::new(name.clone(), token, description, token_type, expires_at);
     vault.add_token(secret)?;
     
-    refresh_session()?;
+    refresh_session(&vault.path)?;
     
     display::success(&format!("Token '{}' added successfully!", name));
     Ok(())
 }
 
-fn refresh_session() -> Result<(), Box<dyn std::error::Error>> {
+fn add_totp(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error::Error>> {
+    println!();
+    display::info("Adding new TOTP (2FA) secret...");
+    println!();
+    
+    let name = input::prompt_text("Name (e.g., 'github-2fa'):")?;
+    if name.is_empty() {
+        display::error("Name is required.");
+        return Ok(());
+    }
+    
+    let issuer = input::prompt_optional("Issuer (optional, e.g., 'GitHub'):")?;
+    
+    let secret = input::prompt_password("Secret (base32):")?;
+    if secret.is_empty() {
+        display::error("Secret is required.");
+        return Ok(());
+    }
+    if let Err(e) = crate::crypto::totp::parse_base32_secret(&secret) {
+        display::error(&e.to_string());
+        return Ok(());
+    }
+    
+    let secret = Totp::new(name.clone(), secret, issuer);
+    vault.add_totp(secret)?;
+    
+    refresh_session(&vault.path)?;
+    
+    display::success(&format!("TOTP '{}' added successfully!", name));
+    Ok(())
+}
+
+fn add_ssh_key(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error::Error>> {
+    println!();
+    display::info("Adding new SSH key...");
+    println!();
+    
+    let name = input::prompt_text("Name (e.g., 'github-deploy'):")?;
+    if name.is_empty() {
+        display::error("Name is required.");
+        return Ok(());
+    }
+    
+    let description = input::prompt_optional("Description (optional):")?;
+    
+    let private_path = input::prompt_text("Private key file (e.g., ~/.ssh/id_ed25519):")?;
+    let private_path = expand_home(&private_path);
+    let private_key = std::fs::read_to_string(&private_path)?;
+    
+    let public_path = PathBuf::from(format!("{}.pub", private_path.display()));
+    let public_key = if public_path.exists() {
+        std::fs::read_to_string(&public_path)?.trim().to_string()
+    } else {
+        input::prompt_text("Public key:")?
+    };
+    
+    let key_type = if public_key.starts_with("ssh-ed25519") {
+        SshKeyType::Ed25519
+    } else if public_key.starts_with("ssh-rsa") {
+        SshKeyType::Rsa
+    } else if public_key.starts_with("ecdsa-") {
+        SshKeyType::Ecdsa
+    } else {
+        input::prompt_text("Key type (ed25519/rsa/ecdsa):")?.parse()?
+    };
+    
+    let passphrase = input::prompt_password("Passphrase (leave empty if none):")?;
+    let passphrase = if passphrase.is_empty() { None } else { Some(passphrase) };
+    
+    let key = SshKey::new(name.clone(), key_type, public_key, private_key, passphrase, description);
+    vault.add_ssh_key(key)?;
+    
+    refresh_session(&vault.path)?;
+    
+    display::success(&format!("SSH key '{}' added successfully!", name));
+    Ok(())
+}
+
+fn add_credit_card(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error::Error>> {
+    println!();
+    display::info("Adding new credit card...");
+    println!();
+    
+    let name = input::prompt_text("Name (e.g., 'personal-visa'):")?;
+    if name.is_empty() {
+        display::error("Name is required.");
+        return Ok(());
+    }
+    
+    let cardholder = input::prompt_text("Cardholder name:")?;
+    let brand = input::prompt_optional("Brand (optional, e.g., 'Visa'):")?;
+    let number = input::prompt_password("Card number:")?;
+    
+    let expiry = input::prompt_text("Expiry (MM/YYYY):")?;
+    let Some((month, year)) = expiry
+        .split_once('/')
+        .and_then(|(m, y)| Some((m.trim().parse().ok()?, y.trim().parse().ok()?)))
+    else {
+        display::error("Expiry must look like 08/2029.");
+        return Ok(());
+    };
+    
+    let cvv = input::prompt_password("CVV:")?;
+    
+    let card = CreditCard::new(name.clone(), cardholder, number, month, year, cvv, brand);
+    vault.add_credit_card(card)?;
+    
+    refresh_session(&vault.path)?;
+    
+    display::success(&format!("Credit card '{}' added successfully!", name));
+    Ok(())
+}
+
+fn add_wifi(vault: &mut crate::vault::Vault) -> Result<(), Box<dyn std::error::Error>> {
+    println!();
+    display::info("Adding new WiFi network...");
+    println!();
+    
+    let name = input::prompt_text("Name (e.g., 'home-wifi'):")?;
+    if name.is_empty() {
+        display::error("Name is required.");
+        return Ok(());
+    }
+    
+    let ssid = input::prompt_text("Network name (SSID):")?;
+    let security: WifiSecurity = input::prompt_optional("Security (wpa2/wpa3/wep/open, default wpa2):")?
+        .map(|s| s.parse())
+        .transpose()?
+        .unwrap_or_default();
+    let password = if security == WifiSecurity::Open {
+        String::new()
+    } else {
+        input::prompt_password("Password:")?
+    };
+    let hidden = input::prompt_confirm("Hidden network?", false)?;
+    
+    let wifi = WifiCredential::new(name.clone(), ssid, password, security, hidden);
+    vault.add_wifi_credential(wifi)?;
+    
+    refresh_session(&vault.path)?;
+    
+    display::success(&format!("WiFi network '{}' added successfully!", name));
+    display::info(&format!("Share it with 'kookie get {} --qr'.", name));
+    Ok(())
+}
+
+/// Warns about a weak password and asks whether to store it anyway
+///
+/// Returns true if the password is strong enough or the user accepts it.
+fn confirm_strength(password: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    let strength = strength::estimate_strength(password);
+    if !strength.is_weak() {
+        return Ok(true);
+    }
+    display::warning(&format!("This password is {}:", strength.label()));
+    for weakness in &strength.weaknesses {
+        println!("      {}", weakness.dimmed());
+    }
+    Ok(input::prompt_confirm("Store it anyway?", false)?)
+}
+
+/// Expands a leading `~` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn refresh_session(vault_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
     // Re-save session to extend timeout
-    if let Some(key) = cache::get_cached_key() {
+    if let Some(key) = cache::get_cached_key(vault_path) {
         let config = cache::load_config();
-        cache::save_session(&key, config.timeout_minutes)?;
+        cache::save_session(&key, vault_path, config.timeout_minutes)?;
     }
     Ok(())
 }
@@ -0,0 +1,24 @@
+//! Breach audit command
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+
+/// Runs the audit command, checking every password against Have I Been Pwned
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    
+    display::info("Checking passwords against Have I Been Pwned (only hash prefixes are sent)...");
+    let breached = vault.audit_passwords()?;
+    
+    if breached.is_empty() {
+        display::success("No stored passwords were found in known breaches.");
+        return Ok(());
+    }
+    
+    display::list_header("Breached passwords", breached.len());
+    for (name, count) in &breached {
+        display::warning(&format!("'{}' appears in {} breaches - rotate it", name, count));
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Export and import commands
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::{display, input};
+use crate::vault::transfer::{self, ImportMode, ImportPlan};
+use crate::vault::types::SecretType;
+use crate::vault::{storage, VaultData};
+use std::fs;
+use std::path::Path;
+
+/// Runs the export command
+///
+/// A non-empty `only` limits an encrypted export to those secret types.
+pub fn export(path: &Path, plaintext: bool, only: &[SecretType]) -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    
+    if path.exists() {
+        display::warning(&format!("{} already exists.", path.display()));
+        if !input::prompt_confirm("Overwrite it?", false)? {
+            display::info("Aborted.");
+            return Ok(());
+        }
+    }
+    
+    if plaintext {
+        display::warning("The export will contain ALL secret values unencrypted!");
+        let confirm = input::prompt_confirm("Write a plaintext export?", false)?;
+        let json = vault.export_plaintext_json(confirm)?;
+        storage::write_atomic(path, json.as_bytes())?;
+        display::success(&format!("Plaintext export written to {}", path.display()));
+        display::warning("Delete this file as soon as you no longer need it.");
+        return Ok(());
+    }
+    
+    display::info("Choose a password to protect the backup.");
+    let password = input::prompt_new_password("Backup password:")?;
+    
+    if only.is_empty() {
+        vault.export_encrypted(path, &password)?;
+    } else {
+        vault.export_encrypted_selective(path, &password, only)?;
+    }
+    
+    display::success(&format!("Encrypted backup written to {}", path.display()));
+    Ok(())
+}
+
+/// Runs the import command
+///
+/// With `dry_run` nothing is imported; the command only shows which secrets
+/// are new, which collide with existing names and which are already present.
+pub fn import(path: &Path, mode: ImportMode, plaintext: bool, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut vault = ensure_unlocked()?;
+    
+    if dry_run {
+        let incoming: VaultData = if plaintext {
+            serde_json::from_str(&fs::read_to_string(path)?)?
+        } else {
+            let password = input::prompt_password("Backup password:")?;
+            transfer::read_encrypted(path, &password)?
+        };
+        print_plan(&vault.plan_import(&incoming));
+        return Ok(());
+    }
+    
+    let report = if plaintext {
+        let json = fs::read_to_string(path)?;
+        vault.import_plaintext_json(&json, mode)?
+    } else {
+        let password = input::prompt_password("Backup password:")?;
+        vault.import_encrypted(path, &password, mode)?
+    };
+    
+    display::success(&format!("Imported {} secrets.", report.imported()));
+    println!(
+        "  added: {}, replaced: {}, renamed: {}, skipped: {}",
+        report.added, report.replaced, report.renamed, report.skipped
+    );
+    
+    Ok(())
+}
+
+fn print_plan(plan: &ImportPlan) {
+    let sections = [
+        ("New", &plan.new),
+        ("Name collisions (resolved by --mode)", &plan.collisions),
+        ("Already in the vault", &plan.duplicates),
+    ];
+    
+    for (title, entries) in sections {
+        display::list_header(title, entries.len());
+        for (secret_type, name) in entries.iter() {
+            println!("  • {} ({})", name, secret_type);
+        }
+    }
+    
+    println!();
+    display::info("Dry run: nothing was imported.");
+}
@@ -1,10 +1,34 @@
 //! Configuration command
 
+use crate::commands::lock::ensure_unlocked;
 use crate::session::cache::{self, SessionConfig};
 use crate::utils::display;
+use crate::vault::lockout::LockoutPolicy;
+use crate::vault::policy::PasswordPolicy;
+
+/// Wrong-password lockout settings from the command line
+pub struct LockoutArgs {
+    /// Wrong passwords before locking out; 0 turns the lockout off
+    pub attempts: u32,
+    pub minutes: u32,
+    pub wipe: bool,
+}
 
 /// Runs the config command
-pub fn run(timeout: Option<u32>, show: bool) -> Result<(), Box<dyn std::error::Error>> {
+pub fn run(
+    timeout: Option<u32>,
+    show: bool,
+    lockout: Option<LockoutArgs>,
+    policy: Option<PasswordPolicy>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(lockout) = lockout {
+        return set_lockout(lockout);
+    }
+    
+    if let Some(policy) = policy {
+        return set_policy(policy);
+    }
+    
     if show {
         let config = cache::load_config();
         println!();
@@ -30,8 +54,69 @@ pub fn run(timeout: Option<u32>, show: bool) -> Result<(), Box<dyn std::error::E
         }
     } else {
         display::info("Usage: kookie config --timeout <minutes>");
+        display::info("       kookie config --lockout <attempts> [--lockout-minutes <minutes>] [--wipe-on-lockout]");
+        display::info("       kookie config --min-length <chars> [--require-symbol] [--require-digit] [--require-mixed-case] [--forbid-common]");
         display::info("       kookie config --show");
     }
     
     Ok(())
 }
+
+/// Stores the lockout policy in the vault file
+fn set_lockout(args: LockoutArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut vault = ensure_unlocked()?;
+    
+    if args.attempts == 0 {
+        vault.set_lockout_policy(None)?;
+        display::success("Lockout disabled.");
+        return Ok(());
+    }
+    
+    vault.set_lockout_policy(Some(LockoutPolicy {
+        max_attempts: args.attempts,
+        cooldown_minutes: args.minutes,
+        wipe: args.wipe,
+    }))?;
+    
+    if args.wipe {
+        display::warning(&format!(
+            "The vault will be WIPED after {} wrong master passwords in a row.",
+            args.attempts
+        ));
+    } else {
+        display::success(&format!(
+            "Unlocking will be refused for {} minutes after {} wrong master passwords in a row.",
+            args.minutes, args.attempts
+        ));
+    }
+    Ok(())
+}
+
+/// Stores the password policy in the vault; an all-zero policy removes it
+fn set_policy(policy: PasswordPolicy) -> Result<(), Box<dyn std::error::Error>> {
+    let mut vault = ensure_unlocked()?;
+    
+    if policy == PasswordPolicy::default() {
+        vault.set_policy(None)?;
+        display::success("Password policy removed.");
+        return Ok(());
+    }
+    
+    let mut rules = vec![format!("at least {} characters", policy.min_length)];
+    if policy.require_symbol {
+        rules.push("a symbol".to_string());
+    }
+    if policy.require_digit {
+        rules.push("a digit".to_string());
+    }
+    if policy.require_mixed_case {
+        rules.push("mixed case".to_string());
+    }
+    if policy.forbid_common {
+        rules.push("not a common password".to_string());
+    }
+    vault.set_policy(Some(policy))?;
+    
+    display::success(&format!("New passwords must have: {}.", rules.join(", ")));
+    Ok(())
+}
@@ -2,6 +2,7 @@
 
 use crate::commands::lock::ensure_unlocked;
 use crate::utils::{display, input};
+use crate::vault::search;
 
 /// Runs the delete command
 pub fn run(id_or_name: &str, force: bool) -> Result<(), Box<dyn std::error::Error>> {
@@ -18,6 +19,14 @@ pub fn run(id_or_name: &str, force: bool) -> Result<(), Box<dyn std::error::Erro
         Some("database credential")
     } else if vault.get_token(id_or_name).is_some() {
         Some("token")
+    } else if vault.get_totp(id_or_name).is_some() {
+        Some("TOTP")
+    } else if vault.get_ssh_key(id_or_name).is_some() {
+        Some("SSH key")
+    } else if vault.get_credit_card(id_or_name).is_some() {
+        Some("credit card")
+    } else if vault.get_wifi_credential(id_or_name).is_some() {
+        Some("WiFi network")
     } else {
         None
     };
@@ -26,6 +35,7 @@ pub fn run(id_or_name: &str, force: bool) -> Result<(), Box<dyn std::error::Erro
         Some(t) => t,
         None => {
             display::error(&format!("Secret '{}' not found.", id_or_name));
+            display::suggestions(&vault.suggest(id_or_name, search::SUGGESTION_COUNT));
             return Ok(());
         }
     };
@@ -53,11 +63,49 @@ pub fn run(id_or_name: &str, force: bool) -> Result<(), Box<dyn std::error::Erro
         vault.delete_db_credential(id_or_name)?.name
     } else if vault.get_token(id_or_name).is_some() {
         vault.delete_token(id_or_name)?.name
+    } else if vault.get_totp(id_or_name).is_some() {
+        vault.delete_totp(id_or_name)?.name
+    } else if vault.get_ssh_key(id_or_name).is_some() {
+        vault.delete_ssh_key(id_or_name)?.name
+    } else if vault.get_credit_card(id_or_name).is_some() {
+        vault.delete_credit_card(id_or_name)?.name
+    } else if vault.get_wifi_credential(id_or_name).is_some() {
+        vault.delete_wifi_credential(id_or_name)?.name
     } else {
         return Ok(());
     };
     
-    display::success(&format!("Deleted {} '{}'", secret_type, deleted_name));
+    display::success(&format!("Moved {} '{}' to the trash", secret_type, deleted_name));
+    display::info("Restore it with 'kookie trash --restore <id>'.");
+    
+    Ok(())
+}
+
+/// Runs the delete command for every secret with a tag
+pub fn run_by_tag(tag: &str, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut vault = ensure_unlocked()?;
+    
+    let tagged = vault.find_by_tag(tag);
+    if tagged.is_empty() {
+        display::info(&format!("No secrets tagged '{}'.", tag));
+        return Ok(());
+    }
+    
+    if !force {
+        display::warning(&format!("You are about to delete {} secrets tagged '{}':", tagged.len(), tag));
+        for (secret_type, name) in &tagged {
+            println!("  {} ({})", name, secret_type);
+        }
+        if !input::prompt_confirm("Are you sure?", false)? {
+            display::info("Aborted.");
+            return Ok(());
+        }
+    }
+    
+    let count = vault.delete_by_tag(tag)?;
+    
+    display::success(&format!("Moved {} secrets to the trash", count));
+    display::info("Restore them with 'kookie trash --restore <id>'.");
     
     Ok(())
 }
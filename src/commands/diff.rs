@@ -0,0 +1,65 @@
+//! Vault comparison command
+
+use crate::utils::{display, input};
+use crate::vault::diff::ChangedSecret;
+use crate::vault::readonly::ReadOnlyVault;
+use crate::vault::types::SecretType;
+use colored::*;
+use std::path::Path;
+
+/// Runs the diff command
+pub fn run(first: &Path, second: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let first_vault = open(first)?;
+    let second_vault = open(second)?;
+    let diff = first_vault.diff(&second_vault);
+    
+    if diff.is_empty() {
+        display::success("Both vaults hold the same secrets.");
+        return Ok(());
+    }
+    
+    print_names(&format!("Only in {}", first.display()), &diff.only_in_self);
+    print_names(&format!("Only in {}", second.display()), &diff.only_in_other);
+    print_changed(&diff.changed);
+    
+    Ok(())
+}
+
+fn open(path: &Path) -> Result<ReadOnlyVault, Box<dyn std::error::Error>> {
+    let password = input::prompt_password(&format!("Master password for {}:", path.display()))?;
+    Ok(ReadOnlyVault::open(path.to_path_buf(), &password)?)
+}
+
+fn print_names(title: &str, names: &[(SecretType, String)]) {
+    if names.is_empty() {
+        return;
+    }
+    
+    display::list_header(title, names.len());
+    for (secret_type, name) in names {
+        println!("  {} {}", name.white().bold(), format!("({})", secret_type).dimmed());
+    }
+}
+
+fn print_changed(changed: &[ChangedSecret]) {
+    if changed.is_empty() {
+        return;
+    }
+    
+    display::list_header("Changed", changed.len());
+    for secret in changed {
+        let mut what = Vec::new();
+        if secret.value_changed {
+            what.push("value");
+        }
+        if secret.updated_at_changed {
+            what.push("updated_at");
+        }
+        println!(
+            "  {} {} {}",
+            secret.name.white().bold(),
+            format!("({})", secret.secret_type).dimmed(),
+            what.join(", ").yellow()
+        );
+    }
+}
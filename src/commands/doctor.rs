@@ -0,0 +1,46 @@
+//! Vault health check command
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+use crate::vault::doctor::{CheckResult, CheckStatus};
+use colored::*;
+
+/// Runs the doctor command
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    let report = vault.doctor();
+    
+    print_section("Passed", &report.with_status(CheckStatus::Pass));
+    print_section("Warnings", &report.with_status(CheckStatus::Warn));
+    print_section("Failures", &report.with_status(CheckStatus::Fail));
+    
+    println!();
+    if report.is_healthy() {
+        display::success("Vault is healthy.");
+    } else if report.has_failures() {
+        display::error("Vault has problems that need attention.");
+    } else {
+        display::warning("Vault is usable but has warnings.");
+    }
+    
+    Ok(())
+}
+
+fn print_section(title: &str, checks: &[&CheckResult]) {
+    if checks.is_empty() {
+        return;
+    }
+    
+    display::list_header(title, checks.len());
+    for check in checks {
+        let marker = match check.status {
+            CheckStatus::Pass => "✓".green().bold(),
+            CheckStatus::Warn => "!".yellow().bold(),
+            CheckStatus::Fail => "✗".red().bold(),
+        };
+        println!("  {} {}", marker, check.name.white().bold());
+        for detail in &check.details {
+            println!("      {}", detail.dimmed());
+        }
+    }
+}
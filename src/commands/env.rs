@@ -0,0 +1,14 @@
+//! Environment variable output command
+
+use crate::commands::lock::ensure_unlocked;
+
+/// Runs the env command, printing `KEY=value` lines to stdout
+pub fn run(names: &[String], prefix: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    let env = match prefix {
+        Some(prefix) => vault.export_env_prefixed(names, prefix)?,
+        None => vault.export_env(names)?,
+    };
+    println!("{}", env);
+    Ok(())
+}
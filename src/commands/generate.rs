@@ -1,18 +1,24 @@
 //! Generate secrets command
 
+use crate::config::Config;
 use crate::utils::{clipboard, display, generators};
 
 /// Type of key to generate
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum GenerateType {
     Jwt,
     Key,
-    Password,
+    Password { exclude_ambiguous: bool, min_digits: usize, min_symbols: usize },
     ApiKey,
+    Passphrase { separator: String, capitalize: bool },
+    Pronounceable { capitalize: bool, digits: usize },
+    Pattern(String),
 }
 
 /// Runs the generate command
-pub fn run(gen_type: GenerateType, length: Option<usize>, copy: bool, symbols: bool) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// Password length and symbols default to the values in `kookie.toml`.
+pub fn run(gen_type: GenerateType, length: Option<usize>, copy: bool, symbols: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
     let (label, value) = match gen_type {
         GenerateType::Jwt => {
             let secret = generators::generate_jwt_secret();
@@ -20,18 +26,40 @@ pub fn run(gen_type: GenerateType, length: Option<usize>, copy: bool, symbols: b
         }
         GenerateType::Key => {
             let len = length.unwrap_or(32);
-            let key = generators::generate_random_key(len);
-            (format!("Random Key ({} bytes)", len), key)
+            let (key, bits) = generators::generate_random_key_with_entropy(len);
+            (format!("Random Key ({} bytes, {} bits)", len, bits), key)
         }
-        GenerateType::Password => {
-            let len = length.unwrap_or(16);
-            let password = generators::generate_password(len, symbols);
+        GenerateType::Password { exclude_ambiguous, min_digits, min_symbols } => {
+            let defaults = Config::current().password_options();
+            let len = length.unwrap_or(defaults.length);
+            let password = generators::generate_password_with(&generators::PasswordOptions {
+                length: len,
+                include_symbols: symbols.unwrap_or(defaults.include_symbols),
+                exclude_ambiguous,
+                min_digits,
+                min_symbols,
+                ..defaults
+            })?;
             (format!("Random Password ({} chars)", len), password)
         }
         GenerateType::ApiKey => {
             let key = generators::generate_api_key();
             ("API Key".to_string(), key)
         }
+        GenerateType::Passphrase { separator, capitalize } => {
+            let words = length.unwrap_or(6);
+            let passphrase = generators::generate_passphrase(words, &separator, capitalize);
+            (format!("Passphrase ({} words)", words), passphrase)
+        }
+        GenerateType::Pronounceable { capitalize, digits } => {
+            let syllables = length.unwrap_or(5);
+            let password = generators::generate_pronounceable(syllables, capitalize, digits);
+            (format!("Pronounceable Password ({} syllables)", syllables), password)
+        }
+        GenerateType::Pattern(pattern) => {
+            let value = generators::generate_from_pattern(&pattern)?;
+            (format!("Pattern {}", pattern), value)
+        }
     };
     
     println!();
@@ -39,7 +67,7 @@ pub fn run(gen_type: GenerateType, length: Option<usize>, copy: bool, symbols: b
     println!();
     
     if copy {
-        clipboard::copy_to_clipboard(&value)?;
+        clipboard::copy_to_clipboard(&value, None)?;
         display::success("Copied to clipboard!");
     }
     
@@ -1,60 +1,175 @@
 //! Get secret command
 
 use crate::commands::lock::ensure_unlocked;
-use crate::utils::{clipboard, display};
+use crate::utils::{clipboard, display, qr};
+use crate::vault::search;
+use crate::vault::types::SecretType;
+use crate::vault::VaultError;
+use chrono::Utc;
+use std::time::Duration;
+
+/// Runs the get command with JSON output for scripts
+///
+/// Secret values are masked unless `reveal` is set.
+pub fn run_json(id_or_name: &str, reveal: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    
+    let json = SecretType::ALL
+        .into_iter()
+        .find_map(|secret_type| vault.get_as_json(secret_type, id_or_name, reveal).ok());
+    match json {
+        Some(json) => println!("{}", serde_json::to_string_pretty(&json)?),
+        None => return Err(VaultError::SecretNotFound(id_or_name.to_string()).into()),
+    }
+    Ok(())
+}
+
+/// Runs the get command printing only the secret value, for piping
+pub fn run_raw(id_or_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    
+    let value = SecretType::ALL
+        .into_iter()
+        .find_map(|secret_type| vault.get_value(secret_type, id_or_name).ok());
+    match value {
+        Some(value) => println!("{}", value),
+        None => return Err(VaultError::SecretNotFound(id_or_name.to_string()).into()),
+    }
+    Ok(())
+}
 
 /// Runs the get command
-pub fn run(id_or_name: &str, copy: bool) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// With `clear_after` set, the command waits and then clears the clipboard
+/// (unless something else has been copied since). With `show_qr` the secret
+/// value is also printed as a QR code; for TOTP secrets the code holds the
+/// `otpauth://` URI so it can be scanned into an authenticator app, and for
+/// WiFi networks the `WIFI:` join string so a phone can connect by scanning.
+pub fn run(id_or_name: &str, copy: bool, show_qr: bool, clear_after: Option<Duration>) -> Result<(), Box<dyn std::error::Error>> {
     let vault = ensure_unlocked()?;
     
     // Search in all secret types
     if let Some(p) = vault.get_password(id_or_name) {
         display::display_password(p, true);
+        if show_qr {
+            print_qr(p.password.expose_secret())?;
+        }
         if copy {
-            clipboard::copy_to_clipboard(&p.password)?;
-            display::success("Password copied to clipboard!");
+            copy_value(p.password.expose_secret(), "Password", clear_after)?;
         }
         return Ok(());
     }
     
     if let Some(k) = vault.get_api_key(id_or_name) {
         display::display_api_key(k, true);
+        if show_qr {
+            print_qr(k.key.expose_secret())?;
+        }
         if copy {
-            clipboard::copy_to_clipboard(&k.key)?;
-            display::success("API key copied to clipboard!");
+            copy_value(k.key.expose_secret(), "API key", clear_after)?;
         }
         return Ok(());
     }
     
     if let Some(n) = vault.get_note(id_or_name) {
         display::display_note(n, true);
+        if show_qr {
+            print_qr(n.content.expose_secret())?;
+        }
         if copy {
-            clipboard::copy_to_clipboard(&n.content)?;
-            display::success("Note content copied to clipboard!");
+            copy_value(n.content.expose_secret(), "Note content", clear_after)?;
         }
         return Ok(());
     }
     
     if let Some(c) = vault.get_db_credential(id_or_name) {
         display::display_db_credential(c, true);
+        if show_qr {
+            print_qr(&c.connection_string())?;
+        }
         if copy {
-            clipboard::copy_to_clipboard(&c.connection_string())?;
-            display::success("Connection string copied to clipboard!");
+            copy_value(&c.connection_string(), "Connection string", clear_after)?;
         }
         return Ok(());
     }
     
     if let Some(t) = vault.get_token(id_or_name) {
         display::display_token(t, true);
+        if show_qr {
+            print_qr(t.token.expose_secret())?;
+        }
+        if copy {
+            copy_value(t.token.expose_secret(), "Token", clear_after)?;
+        }
+        return Ok(());
+    }
+    
+    if let Some(t) = vault.get_totp(id_or_name) {
+        display::display_totp(t, true);
+        if show_qr {
+            print_qr(&t.to_otpauth_uri())?;
+        }
+        if copy {
+            copy_value(&t.current_code(Utc::now())?, "TOTP code", clear_after)?;
+        }
+        return Ok(());
+    }
+    
+    if let Some(k) = vault.get_ssh_key(id_or_name) {
+        display::display_ssh_key(k, true);
+        if show_qr {
+            print_qr(k.private_key.expose_secret())?;
+        }
         if copy {
-            clipboard::copy_to_clipboard(&t.token)?;
-            display::success("Token copied to clipboard!");
+            copy_value(k.private_key.expose_secret(), "Private key", clear_after)?;
+        }
+        return Ok(());
+    }
+    
+    if let Some(c) = vault.get_credit_card(id_or_name) {
+        display::display_credit_card(c, true);
+        if show_qr {
+            print_qr(c.number.expose_secret())?;
+        }
+        if copy {
+            copy_value(c.number.expose_secret(), "Card number", clear_after)?;
+        }
+        return Ok(());
+    }
+    
+    if let Some(w) = vault.get_wifi_credential(id_or_name) {
+        display::display_wifi_credential(w, true);
+        if show_qr {
+            print_qr(&w.to_wifi_qr_string())?;
+        }
+        if copy {
+            copy_value(w.password.expose_secret(), "WiFi password", clear_after)?;
         }
         return Ok(());
     }
     
     display::error(&format!("Secret '{}' not found.", id_or_name));
+    display::suggestions(&vault.suggest(id_or_name, search::SUGGESTION_COUNT));
     display::info("Use 'kookie list' to see all secrets.");
     
     Ok(())
 }
+
+fn print_qr(value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    println!();
+    println!("{}", qr::render_qr_ascii(value)?);
+    Ok(())
+}
+
+fn copy_value(value: &str, what: &str, clear_after: Option<Duration>) -> Result<(), Box<dyn std::error::Error>> {
+    let guard = clipboard::copy_to_clipboard(value, clear_after)?;
+    display::success(&format!("{} copied to clipboard!", what));
+    
+    if let (Some(guard), Some(delay)) = (guard, clear_after) {
+        display::info(&format!("Clipboard will be cleared in {} seconds...", delay.as_secs()));
+        guard.wait();
+        display::info("Clipboard cleared.");
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Vault activity timeline command
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+use crate::vault::timeline::TimelineKind;
+use colored::*;
+
+/// Runs the history command, showing at most `limit` of the latest events
+pub fn run(limit: Option<usize>) -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    let events = vault.timeline();
+    let skip = limit.map_or(0, |limit| events.len().saturating_sub(limit));
+    let shown = &events[skip..];
+    
+    if shown.is_empty() {
+        display::info("No secrets yet.");
+        return Ok(());
+    }
+    
+    display::list_header("History", shown.len());
+    for event in shown {
+        let kind = match event.kind {
+            TimelineKind::Created => event.kind.to_string().green(),
+            TimelineKind::Updated => event.kind.to_string().yellow(),
+        };
+        println!(
+            "  {}  {:<8} {} {}",
+            event.at.format("%Y-%m-%d %H:%M").to_string().dimmed(),
+            kind,
+            event.name.white().bold(),
+            format!("({})", event.secret_type).dimmed()
+        );
+    }
+    
+    Ok(())
+}
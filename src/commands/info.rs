@@ -0,0 +1,29 @@
+//! Vault header info command
+
+use crate::crypto::kdf;
+use crate::vault::storage;
+use colored::*;
+
+/// Runs the info command; reads only the unencrypted header, no password needed
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let path = storage::get_vault_path()?;
+    if !path.exists() {
+        return Err("Vault not initialized. Run 'kookie init' first.".into());
+    }
+    let header = storage::read_vault_header(&path)?;
+    
+    println!();
+    println!("{}", "═".repeat(50).dimmed());
+    println!("{} {}", "Path:".dimmed(), path.display().to_string().cyan());
+    println!("{} {}", "Version:".dimmed(), header.version);
+    println!("{} {}", "Cipher:".dimmed(), header.algorithm);
+    match header.salt_bytes {
+        Some(bytes) => println!("{} {} bytes, {}", "Salt:".dimmed(), bytes, kdf::SALT_ENCODING),
+        None => println!("{} {}", "Salt:".dimmed(), format!("corrupt (not {})", kdf::SALT_ENCODING).red()),
+    }
+    println!("{} {}", "Created:".dimmed(), header.created_at.format("%Y-%m-%d %H:%M"));
+    println!("{} {}", "Modified:".dimmed(), header.modified_at.format("%Y-%m-%d %H:%M"));
+    println!("{}", "═".repeat(50).dimmed());
+    
+    Ok(())
+}
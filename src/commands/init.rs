@@ -1,11 +1,18 @@
 //! Initialize vault command
 
+use crate::commands;
+use crate::crypto::Algorithm;
 use crate::utils::{display, input};
 use crate::vault::Vault;
 
 /// Runs the init command
-pub fn run(force: bool) -> Result<(), Box<dyn std::error::Error>> {
-    let mut vault = Vault::new();
+pub fn run(force: bool, cipher: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut vault = Vault::new()?;
+    let algorithm = match cipher {
+        Some(id) => id.parse::<Algorithm>()?,
+        None => Algorithm::default(),
+    };
+    vault.set_algorithm(algorithm);
     
     if vault.exists() && !force {
         display::error("Vault already exists at ~/.kookie/vault.json");
@@ -27,18 +34,29 @@ pub fn run(force: bool) -> Result<(), Box<dyn std::error::Error>> {
     
     // Prompt for master password
     let password = input::prompt_new_password("Enter master password:")?;
+    let with_recovery_key =
+        input::prompt_confirm("Generate a recovery key in case you forget the master password?", true)?;
     
     // Initialize vault
-    if force {
-        vault.init_force(&password)?;
+    let result = if force {
+        vault.init_force_returning(&password, with_recovery_key)?
     } else {
-        vault.init(&password)?;
-    }
+        vault.init_returning(&password, with_recovery_key)?
+    };
     
     println!();
     display::success("Vault initialized successfully!");
+    display::info(&format!("Encrypted with {}", algorithm));
     display::info("Your encrypted vault is stored at ~/.kookie/vault.json");
-    display::info("Remember your master password - it cannot be recovered!");
+    
+    match result.recovery_key {
+        Some(recovery_key) => commands::recovery::show(recovery_key.expose_secret()),
+        None => {
+            println!();
+            display::info("Remember your master password - without a recovery key it cannot be recovered!");
+            display::info("You can create one later with 'kookie recovery-key'.");
+        }
+    }
     
     Ok(())
 }
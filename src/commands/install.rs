@@ -5,7 +5,7 @@
 use crate::utils::display;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(windows)]
 use std::process::Command;
@@ -87,7 +87,7 @@ fn add_to_path_windows(install_dir: &PathBuf) -> Result<bool, Box<dyn std::error
 
 /// Adds to PATH on Unix systems
 #[cfg(not(windows))]
-fn add_to_path_unix(install_dir: &PathBuf) -> Result<bool, Box<dyn std::error::Error>> {
+fn add_to_path_unix(install_dir: &Path) -> Result<bool, Box<dyn std::error::Error>> {
     let install_dir_str = install_dir.to_string_lossy();
     
     // Check if already in PATH
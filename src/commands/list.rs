@@ -1,7 +1,12 @@
 //! List secrets command
 
 use crate::commands::lock::ensure_unlocked;
-use crate::utils::display;
+use crate::utils::{display, redact};
+use crate::vault::types::{SecretEntry, SortKey};
+use chrono::Duration;
+
+/// How far ahead to warn about tokens that are about to expire
+const TOKEN_EXPIRY_WARNING_DAYS: i64 = 7;
 
 /// Type filter for listing
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,64 +17,107 @@ pub enum ListFilter {
     Notes,
     DbCredentials,
     Tokens,
+    Totps,
+    SshKeys,
+    CreditCards,
+    Wifi,
 }
 
 /// Runs the list command
-pub fn run(filter: ListFilter) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// Without `sort`, secrets are shown in the order they were added.
+pub fn run(filter: ListFilter, sort: Option<SortKey>, desc: bool) -> Result<(), Box<dyn std::error::Error>> {
     let vault = ensure_unlocked()?;
     
     let mut total = 0;
     
-    if filter == ListFilter::All || filter == ListFilter::Passwords {
-        if !vault.data.passwords.is_empty() {
-            display::list_header("Passwords", vault.data.passwords.len());
-            for p in &vault.data.passwords {
-                display::list_item(&p.id, &p.name, p.username.as_deref());
-            }
-            total += vault.data.passwords.len();
+    if (filter == ListFilter::All || filter == ListFilter::Passwords) && !vault.list_passwords().is_empty() {
+        display::list_header("Passwords", vault.list_passwords().len());
+        for p in ordered(vault.list_passwords(), sort, desc) {
+            display::list_item(&p.id, &p.name, p.username.as_deref());
+        }
+        total += vault.list_passwords().len();
+    }
+    
+    if (filter == ListFilter::All || filter == ListFilter::ApiKeys) && !vault.list_api_keys().is_empty() {
+        display::list_header("API Keys", vault.list_api_keys().len());
+        for k in ordered(vault.list_api_keys(), sort, desc) {
+            let key = k.key.redacted(redact::DEFAULT_VISIBLE);
+            let extra = match &k.service {
+                Some(service) => format!("{}, {}", service, key),
+                None => key,
+            };
+            display::list_item(&k.id, &k.name, Some(&extra));
+        }
+        total += vault.list_api_keys().len();
+    }
+    
+    if (filter == ListFilter::All || filter == ListFilter::Notes) && !vault.list_notes().is_empty() {
+        display::list_header("Notes", vault.list_notes().len());
+        for n in ordered(vault.list_notes(), sort, desc) {
+            display::list_item(&n.id, &n.name, None);
+        }
+        total += vault.list_notes().len();
+    }
+    
+    if (filter == ListFilter::All || filter == ListFilter::DbCredentials) && !vault.list_db_credentials().is_empty() {
+        display::list_header("Database Credentials", vault.list_db_credentials().len());
+        for c in ordered(vault.list_db_credentials(), sort, desc) {
+            let extra = format!("{}@{}", c.username, c.host);
+            display::list_item(&c.id, &c.name, Some(&extra));
         }
+        total += vault.list_db_credentials().len();
     }
     
-    if filter == ListFilter::All || filter == ListFilter::ApiKeys {
-        if !vault.data.api_keys.is_empty() {
-            display::list_header("API Keys", vault.data.api_keys.len());
-            for k in &vault.data.api_keys {
-                display::list_item(&k.id, &k.name, k.service.as_deref());
-            }
-            total += vault.data.api_keys.len();
+    if (filter == ListFilter::All || filter == ListFilter::Tokens) && !vault.list_tokens().is_empty() {
+        display::list_header("Tokens", vault.list_tokens().len());
+        for t in ordered(vault.list_tokens(), sort, desc) {
+            let token = t.token.redacted(redact::DEFAULT_VISIBLE);
+            let extra = if t.is_expired() { format!("{}, expired", token) } else { token };
+            display::list_item(&t.id, &t.name, Some(&extra));
+        }
+        total += vault.list_tokens().len();
+        
+        for t in vault.expired_tokens() {
+            display::warning(&format!("Token '{}' has expired", t.name));
+        }
+        for t in vault.tokens_expiring_within(Duration::days(TOKEN_EXPIRY_WARNING_DAYS)) {
+            display::warning(&format!("Token '{}' expires within {} days", t.name, TOKEN_EXPIRY_WARNING_DAYS));
         }
     }
     
-    if filter == ListFilter::All || filter == ListFilter::Notes {
-        if !vault.data.notes.is_empty() {
-            display::list_header("Notes", vault.data.notes.len());
-            for n in &vault.data.notes {
-                display::list_item(&n.id, &n.name, None);
-            }
-            total += vault.data.notes.len();
+    if (filter == ListFilter::All || filter == ListFilter::Totps) && !vault.list_totps().is_empty() {
+        display::list_header("TOTP", vault.list_totps().len());
+        for t in ordered(vault.list_totps(), sort, desc) {
+            display::list_item(&t.id, &t.name, t.issuer.as_deref());
         }
+        total += vault.list_totps().len();
     }
     
-    if filter == ListFilter::All || filter == ListFilter::DbCredentials {
-        if !vault.data.db_credentials.is_empty() {
-            display::list_header("Database Credentials", vault.data.db_credentials.len());
-            for c in &vault.data.db_credentials {
-                let extra = format!("{}@{}", c.username, c.host);
-                display::list_item(&c.id, &c.name, Some(&extra));
-            }
-            total += vault.data.db_credentials.len();
+    if (filter == ListFilter::All || filter == ListFilter::SshKeys) && !vault.list_ssh_keys().is_empty() {
+        display::list_header("SSH Keys", vault.list_ssh_keys().len());
+        for k in ordered(vault.list_ssh_keys(), sort, desc) {
+            let extra = k.key_type.to_string();
+            display::list_item(&k.id, &k.name, Some(&extra));
         }
+        total += vault.list_ssh_keys().len();
     }
     
-    if filter == ListFilter::All || filter == ListFilter::Tokens {
-        if !vault.data.tokens.is_empty() {
-            display::list_header("Tokens", vault.data.tokens.len());
-            for t in &vault.data.tokens {
-                let extra = if t.is_expired() { Some("expired") } else { None };
-                display::list_item(&t.id, &t.name, extra);
-            }
-            total += vault.data.tokens.len();
+    if (filter == ListFilter::All || filter == ListFilter::CreditCards) && !vault.list_credit_cards().is_empty() {
+        display::list_header("Credit Cards", vault.list_credit_cards().len());
+        for c in ordered(vault.list_credit_cards(), sort, desc) {
+            let extra = format!("•••• {}", c.last_four());
+            display::list_item(&c.id, &c.name, Some(&extra));
         }
+        total += vault.list_credit_cards().len();
+    }
+    
+    if (filter == ListFilter::All || filter == ListFilter::Wifi) && !vault.list_wifi_credentials().is_empty() {
+        display::list_header("WiFi Networks", vault.list_wifi_credentials().len());
+        for w in ordered(vault.list_wifi_credentials(), sort, desc) {
+            display::list_item(&w.id, &w.name, Some(&w.ssid));
+        }
+        total += vault.list_wifi_credentials().len();
     }
     
     if total == 0 {
@@ -81,3 +129,10 @@ pub fn run(filter: ListFilter) -> Result<(), Box<dyn std::error::Error>> {
     
     Ok(())
 }
+
+fn ordered<T: SecretEntry>(items: &[T], sort: Option<SortKey>, desc: bool) -> Vec<&T> {
+    match sort {
+        Some(key) => key.sort(items, desc),
+        None => items.iter().collect(),
+    }
+}
@@ -4,6 +4,9 @@ use crate::session::{self, cache};
 use crate::utils::{display, input};
 use crate::vault::Vault;
 
+/// Master password prompts before giving up
+const MAX_UNLOCK_ATTEMPTS: u32 = 3;
+
 /// Runs the lock command
 pub fn lock() -> Result<(), Box<dyn std::error::Error>> {
     cache::clear_session()?;
@@ -13,7 +16,7 @@ pub fn lock() -> Result<(), Box<dyn std::error::Error>> {
 
 /// Runs the unlock command
 pub fn unlock(timeout: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
-    let mut vault = Vault::new();
+    let mut vault = Vault::new()?;
     
     if !vault.exists() {
         display::error("Vault not initialized. Run 'kookie init' first.");
@@ -25,24 +28,23 @@ pub fn unlock(timeout: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
     let timeout_minutes = timeout.unwrap_or(config.timeout_minutes);
     
     // Check if already unlocked
-    if let Some(_key) = cache::get_cached_key() {
+    if let Some(_key) = cache::get_cached_key(&vault.path) {
         display::info("Vault is already unlocked.");
         return Ok(());
     }
     
-    // Prompt for password
-    let password = input::prompt_password("Enter master password:")?;
-    
     // Try to unlock
-    match vault.unlock(&password) {
-        Ok(()) => {
+    match unlock_with_prompt(&mut vault) {
+        Ok(password) => {
             // Save session
             if timeout_minutes > 0 {
                 // We need to get the key from the vault - but it's private
                 // So we'll re-derive it here
-                let vault_file = crate::vault::storage::load_vault_file(&vault.path)?;
-                let key = crate::crypto::kdf::derive_key(&password, &vault_file.salt)?;
-                session::save_session(&key, timeout_minutes)?;
+                let vault_file = crate::vault::migrate::migrate(
+                    crate::vault::storage::load_vault_file(&vault.path)?,
+                )?;
+                let (key, _) = vault_file.open(&password)?;
+                session::save_session(&key, &vault.path, timeout_minutes)?;
                 
                 display::success(&format!(
                     "Vault unlocked for {} minutes.",
@@ -51,6 +53,14 @@ pub fn unlock(timeout: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
             } else {
                 display::success("Vault unlocked (session disabled).");
             }
+            
+            let due = vault.rotation_due();
+            if !due.is_empty() {
+                let names: Vec<String> = due.iter()
+                    .map(|(secret_type, name)| format!("{} ({})", name, secret_type))
+                    .collect();
+                display::warning(&format!("Due for rotation: {}", names.join(", ")));
+            }
             Ok(())
         }
         Err(e) => {
@@ -60,36 +70,79 @@ pub fn unlock(timeout: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
     }
 }
 
+/// Runs the change-password command
+pub fn change_password() -> Result<(), Box<dyn std::error::Error>> {
+    let mut vault = Vault::new()?;
+    
+    if !vault.exists() {
+        display::error("Vault not initialized. Run 'kookie init' first.");
+        return Ok(());
+    }
+    
+    let old = input::prompt_password("Current master password:")?;
+    let new = input::prompt_new_password("New master password:")?;
+    
+    vault.change_master_password(&old, &new)?;
+    
+    // The cached session key belongs to the old password
+    cache::clear_session()?;
+    
+    display::success("Master password changed. Please unlock again with the new password.");
+    Ok(())
+}
+
 /// Ensures the vault is unlocked, prompting for password if needed
 /// Returns the unlocked vault
 pub fn ensure_unlocked() -> Result<Vault, Box<dyn std::error::Error>> {
-    let mut vault = Vault::new();
+    let mut vault = Vault::new()?;
     
     if !vault.exists() {
         return Err("Vault not initialized. Run 'kookie init' first.".into());
     }
     
     // Check for cached session
-    if let Some(key) = cache::get_cached_key() {
+    if let Some(key) = cache::get_cached_key(&vault.path) {
         // Load vault with cached key
-        let vault_file = crate::vault::storage::load_vault_file(&vault.path)?;
-        let decrypted = crate::crypto::decrypt(&key, &vault_file.encrypted_data)
+        vault.unlock_with_key(key)
             .map_err(|_| "Session expired or corrupted. Please unlock again.")?;
-        vault.data = serde_json::from_slice(&decrypted)?;
         return Ok(vault);
     }
     
     // Prompt for password
-    let password = input::prompt_password("Enter master password:")?;
-    vault.unlock(&password)?;
+    let password = unlock_with_prompt(&mut vault)?;
     
     // Save session for convenience
     let config = cache::load_config();
     if config.timeout_minutes > 0 {
-        let vault_file = crate::vault::storage::load_vault_file(&vault.path)?;
-        let key = crate::crypto::kdf::derive_key(&password, &vault_file.salt)?;
-        session::save_session(&key, config.timeout_minutes)?;
+        let vault_file = crate::vault::migrate::migrate(
+            crate::vault::storage::load_vault_file(&vault.path)?,
+        )?;
+        let (key, _) = vault_file.open(&password)?;
+        session::save_session(&key, &vault.path, config.timeout_minutes)?;
     }
     
     Ok(vault)
 }
+
+/// Unlocks with the master password, re-prompting after a wrong one
+///
+/// Only an interactive prompt is retried; a password from the environment or
+/// stdin gets a single attempt. Returns the password that worked.
+fn unlock_with_prompt(vault: &mut Vault) -> Result<String, crate::vault::VaultError> {
+    if !input::master_password_is_prompted() {
+        let password = input::read_master_password()?;
+        vault.unlock(&password)?;
+        return Ok(password);
+    }
+    
+    let mut password = String::new();
+    vault.unlock_with_retries(|attempt| {
+        if attempt > 1 {
+            display::error("Wrong master password, try again.");
+        }
+        password = input::read_master_password()?;
+        Ok(password.clone())
+    }, MAX_UNLOCK_ATTEMPTS)?;
+    
+    Ok(password)
+}
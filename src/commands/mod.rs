@@ -1,11 +1,24 @@
 //! CLI command implementations
 
 pub mod add;
+#[cfg(feature = "hibp")]
+pub mod audit;
+pub mod backup;
 pub mod config;
 pub mod delete;
+pub mod diff;
+pub mod doctor;
+pub mod env;
 pub mod generate;
+pub mod history;
 pub mod get;
+pub mod info;
 pub mod init;
 pub mod install;
 pub mod list;
 pub mod lock;
+pub mod otp;
+pub mod recovery;
+pub mod search;
+pub mod stats;
+pub mod trash;
@@ -0,0 +1,29 @@
+//! Current TOTP code command
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::{clipboard, display};
+use crate::vault::search;
+use colored::*;
+
+/// Runs the otp command, printing the code and how long it stays valid
+pub fn run(id_or_name: &str, copy: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    
+    if vault.get_totp(id_or_name).is_none() {
+        display::error(&format!("TOTP secret '{}' not found.", id_or_name));
+        display::suggestions(&vault.suggest(id_or_name, search::SUGGESTION_COUNT));
+        return Ok(());
+    }
+    
+    let (code, remaining) = vault.totp_code(id_or_name)?;
+    println!();
+    println!("{} {}", code.yellow().bold(), format!("(expires in {}s)", remaining).dimmed());
+    println!();
+    
+    if copy {
+        clipboard::copy_to_clipboard(&code, None)?;
+        display::success("Copied to clipboard!");
+    }
+    
+    Ok(())
+}
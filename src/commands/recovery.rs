@@ -0,0 +1,59 @@
+//! Recovery key commands
+
+use crate::commands::lock::ensure_unlocked;
+use crate::session::cache;
+use crate::utils::{display, input};
+use crate::vault::Vault;
+use colored::*;
+
+/// Runs the recovery-key command, creating (or replacing) the recovery key
+pub fn generate() -> Result<(), Box<dyn std::error::Error>> {
+    let mut vault = ensure_unlocked()?;
+    
+    if vault.has_recovery_key() {
+        display::warning("This vault already has a recovery key. A new one replaces it.");
+        if !input::prompt_confirm("Generate a new recovery key?", false)? {
+            display::info("Aborted.");
+            return Ok(());
+        }
+    }
+    
+    let recovery_key = vault.generate_recovery_key()?;
+    
+    // The vault may have switched to a new data key
+    cache::clear_session()?;
+    
+    show(&recovery_key);
+    Ok(())
+}
+
+/// Prints a freshly generated recovery key with instructions
+pub fn show(recovery_key: &str) {
+    println!();
+    println!("{}", "═".repeat(50).dimmed());
+    println!("  {}", recovery_key.yellow().bold());
+    println!("{}", "═".repeat(50).dimmed());
+    println!();
+    display::warning("Write this recovery key down and keep it somewhere safe.");
+    display::info("It is shown only once and unlocks the vault if you forget the master password.");
+    display::info("Use it with 'kookie recover' to set a new master password.");
+}
+
+/// Runs the recover command, setting a new master password with the recovery key
+pub fn recover() -> Result<(), Box<dyn std::error::Error>> {
+    let mut vault = Vault::new()?;
+    
+    if !vault.exists() {
+        display::error("Vault not initialized. Run 'kookie init' first.");
+        return Ok(());
+    }
+    
+    let recovery_key = input::prompt_password("Recovery key:")?;
+    let new = input::prompt_new_password("New master password:")?;
+    
+    vault.reset_master_password(&recovery_key, &new)?;
+    cache::clear_session()?;
+    
+    display::success("Master password reset. Unlock with the new password.");
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! Search secrets command
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+use crate::vault::search::SearchOptions;
+use crate::vault::types::SecretType;
+
+/// Runs the search command; an empty `types` searches every type
+pub fn run(query: &str, types: &[SecretType], limit: Option<usize>) -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    let options = SearchOptions {
+        query: query.to_string(),
+        types: (!types.is_empty()).then(|| types.to_vec()),
+        limit,
+    };
+    let hits = vault.search_with(&options);
+    
+    if hits.is_empty() {
+        display::info(&format!("No secrets match '{}'.", query));
+        return Ok(());
+    }
+    
+    display::list_header("Search Results", hits.len());
+    for hit in &hits {
+        let extra = format!("{}, matched {}", hit.secret_type, hit.field);
+        display::list_item(&hit.id, &hit.name, Some(&extra));
+    }
+    println!();
+    
+    Ok(())
+}
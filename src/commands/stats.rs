@@ -0,0 +1,31 @@
+//! Vault statistics command
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::display;
+use colored::*;
+
+/// Runs the stats command
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let vault = ensure_unlocked()?;
+    let stats = vault.stats();
+    
+    display::list_header("Secrets", stats.total);
+    for (secret_type, count) in &stats.counts {
+        if *count > 0 {
+            println!("  {:<16} {}", secret_type.to_string().dimmed(), count);
+        }
+    }
+    
+    if let (Some(oldest), Some(newest)) = (stats.oldest, stats.newest) {
+        println!();
+        println!("  {:<16} {}", "Oldest".dimmed(), oldest.format("%Y-%m-%d"));
+        println!("  {:<16} {}", "Newest".dimmed(), newest.format("%Y-%m-%d"));
+    }
+    
+    if stats.expired_tokens > 0 {
+        println!();
+        display::warning(&format!("{} expired tokens", stats.expired_tokens));
+    }
+    
+    Ok(())
+}
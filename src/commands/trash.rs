@@ -0,0 +1,64 @@
+//! Trash command (list, restore and empty deleted secrets)
+
+use crate::commands::lock::ensure_unlocked;
+use crate::utils::{display, input};
+
+/// Runs the trash command
+pub fn run(restore: Option<&str>, empty: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut vault = ensure_unlocked()?;
+    
+    if let Some(prefix) = restore {
+        // Accept the short id shown in the listing as well as the full one
+        let matches: Vec<_> = vault
+            .list_trash()
+            .iter()
+            .map(|t| t.item.entry())
+            .filter(|e| e.id().starts_with(prefix) || e.name() == prefix)
+            .map(|e| (e.id().to_string(), e.name().to_string()))
+            .collect();
+        
+        match matches.as_slice() {
+            [(id, name)] => {
+                vault.restore(id)?;
+                display::success(&format!("Restored '{}'", name));
+            }
+            [] => display::error(&format!("Nothing in the trash matches '{}'.", prefix)),
+            _ => display::error(&format!("'{}' matches several trashed secrets; use a longer id.", prefix)),
+        }
+        return Ok(());
+    }
+    
+    if empty {
+        if vault.list_trash().is_empty() {
+            display::info("Trash is already empty.");
+            return Ok(());
+        }
+        if !input::prompt_confirm("Permanently delete everything in the trash?", false)? {
+            display::info("Aborted.");
+            return Ok(());
+        }
+        let count = vault.empty_trash()?;
+        display::success(&format!("Permanently deleted {} secrets.", count));
+        return Ok(());
+    }
+    
+    if vault.list_trash().is_empty() {
+        display::info("Trash is empty.");
+        return Ok(());
+    }
+    
+    display::list_header("Trash", vault.list_trash().len());
+    for trashed in vault.list_trash() {
+        let entry = trashed.item.entry();
+        let extra = format!(
+            "{}, deleted {}",
+            entry.secret_type(),
+            trashed.deleted_at.format("%Y-%m-%d %H:%M")
+        );
+        display::list_item(entry.id(), entry.name(), Some(&extra));
+    }
+    println!();
+    display::info("Restore with 'kookie trash --restore <id>'.");
+    
+    Ok(())
+}
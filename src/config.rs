@@ -0,0 +1,156 @@
+//! User defaults from `kookie.toml`
+//!
+//! The file lives in the platform config directory (`~/.config/kookie/` on
+//! Linux) and every key is optional:
+//!
+//! ```toml
+//! password_length = 24
+//! include_symbols = true
+//! clipboard_clear_seconds = 30
+//! default_profile = "work"
+//! ```
+//!
+//! A missing file means the built-in defaults.
+
+use crate::utils::generators::PasswordOptions;
+use crate::vault::storage::DEFAULT_PROFILE;
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the config file inside the config directory
+pub const CONFIG_FILE: &str = "kookie.toml";
+
+/// Config errors
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("Invalid config file: {0}")]
+    ParseError(#[from] toml::de::Error),
+}
+
+/// Defaults that can be overridden in `kookie.toml`
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Length of generated passwords
+    pub password_length: usize,
+    /// Whether generated passwords include symbols
+    pub include_symbols: bool,
+    /// Clear the clipboard this many seconds after copying a secret
+    pub clipboard_clear_seconds: Option<u64>,
+    /// Profile whose vault is used when none is given
+    pub default_profile: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let password = PasswordOptions::default();
+        Self {
+            password_length: password.length,
+            include_symbols: password.include_symbols,
+            clipboard_clear_seconds: None,
+            default_profile: DEFAULT_PROFILE.to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads `kookie.toml` from the config directory
+    pub fn load() -> Result<Self, ConfigError> {
+        match config_path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Like `load`, but an unreadable or invalid file also means defaults
+    pub fn current() -> Self {
+        Self::load().unwrap_or_default()
+    }
+
+    /// Loads a config file, falling back to defaults if it doesn't exist
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Password generator options with this config's length and symbols
+    pub fn password_options(&self) -> PasswordOptions {
+        PasswordOptions {
+            length: self.password_length,
+            include_symbols: self.include_symbols,
+            ..Default::default()
+        }
+    }
+
+    /// Clipboard auto-clear delay, if configured
+    pub fn clipboard_clear_after(&self) -> Option<std::time::Duration> {
+        self.clipboard_clear_seconds
+            .map(std::time::Duration::from_secs)
+    }
+}
+
+/// Returns the path of `kookie.toml`, if there is a config directory
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("kookie").join(CONFIG_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generators;
+
+    #[test]
+    fn test_sample_config_drives_generator_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            "password_length = 40\ninclude_symbols = true\nclipboard_clear_seconds = 15\ndefault_profile = \"work\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.clipboard_clear_seconds, Some(15));
+        assert_eq!(config.default_profile, "work");
+
+        let opts = config.password_options();
+        assert_eq!(opts.length, 40);
+        assert!(opts.include_symbols);
+        let password = generators::generate_password_with(&opts).unwrap();
+        assert_eq!(password.chars().count(), 40);
+    }
+
+    #[test]
+    fn test_missing_or_partial_config_uses_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = Config::load_from(&dir.path().join(CONFIG_FILE)).unwrap();
+        assert_eq!(missing, Config::default());
+        assert_eq!(missing.password_options(), PasswordOptions::default());
+
+        let path = dir.path().join("partial.toml");
+        fs::write(&path, "include_symbols = true\n").unwrap();
+        let partial = Config::load_from(&path).unwrap();
+        assert!(partial.include_symbols);
+        assert_eq!(partial.password_length, PasswordOptions::default().length);
+        assert_eq!(partial.default_profile, DEFAULT_PROFILE);
+    }
+
+    #[test]
+    fn test_rejects_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        fs::write(&path, "password_lenght = 20\n").unwrap();
+        assert!(matches!(
+            Config::load_from(&path),
+            Err(ConfigError::ParseError(_))
+        ));
+    }
+}
@@ -1,14 +1,25 @@
-//! AES-256-GCM Encryption/Decryption
+//! AEAD Encryption/Decryption
 //!
-//! AES-256-GCM provides authenticated encryption, ensuring both
-//! confidentiality and integrity of the encrypted data.
+//! AES-256-GCM (the default) and ChaCha20-Poly1305 both provide
+//! authenticated encryption, ensuring both confidentiality and integrity of
+//! the encrypted data. ChaCha20-Poly1305 is faster on hardware without AES-NI.
 
 use aes_gcm::{
-    aead::{Aead, KeyInit},
+    aead::{
+        consts::U12,
+        generic_array::GenericArray,
+        stream::{DecryptorBE32, EncryptorBE32},
+        Aead, AeadCore, AeadInPlace, KeyInit,
+    },
     Aes256Gcm, Nonce,
 };
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::ChaCha20Poly1305;
 use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
 use thiserror::Error;
 
 /// Cipher errors
@@ -20,31 +31,103 @@ pub enum CipherError {
     DecryptionFailed,
     #[error("Invalid ciphertext format")]
     InvalidFormat,
+    #[error("Unknown cipher algorithm: {0}")]
+    UnknownAlgorithm(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
-/// Nonce size for AES-GCM (96 bits = 12 bytes)
+/// Supported AEAD ciphers
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    #[default]
+    #[serde(rename = "aes-256-gcm")]
+    Aes256Gcm,
+    #[serde(rename = "chacha20-poly1305")]
+    ChaCha20Poly1305,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 2] = [Algorithm::Aes256Gcm, Algorithm::ChaCha20Poly1305];
+
+    /// Identifier stored in vault files and accepted on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            Algorithm::Aes256Gcm => "aes-256-gcm",
+            Algorithm::ChaCha20Poly1305 => "chacha20-poly1305",
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = CipherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Algorithm::ALL
+            .into_iter()
+            .find(|a| a.id().eq_ignore_ascii_case(s))
+            .ok_or_else(|| CipherError::UnknownAlgorithm(s.to_string()))
+    }
+}
+
+/// Nonce size shared by AES-GCM and ChaCha20-Poly1305 (96 bits = 12 bytes)
 const NONCE_SIZE: usize = 12;
 
-/// Encrypts plaintext using AES-256-GCM
+/// Plaintext bytes per chunk in streaming mode
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Authentication tag appended to every chunk
+const TAG_SIZE: usize = 16;
+
+/// Random nonce prefix for STREAM; the remaining 5 bytes hold a 32-bit
+/// chunk counter and the last-chunk flag
+const STREAM_NONCE_PREFIX_SIZE: usize = NONCE_SIZE - 5;
+
+/// Encrypts plaintext using the default algorithm (AES-256-GCM)
+pub fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<String, CipherError> {
+    encrypt_with(Algorithm::default(), key, plaintext)
+}
+
+/// Decrypts ciphertext using the default algorithm (AES-256-GCM)
+pub fn decrypt(key: &[u8; 32], ciphertext_b64: &str) -> Result<Vec<u8>, CipherError> {
+    decrypt_with(Algorithm::default(), key, ciphertext_b64)
+}
+
+/// Encrypts plaintext using the given algorithm
 ///
 /// # Arguments
+/// * `algorithm` - Cipher to use
 /// * `key` - 32-byte encryption key
 /// * `plaintext` - Data to encrypt
 ///
 /// # Returns
 /// Base64-encoded string containing: nonce || ciphertext || tag
-pub fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<String, CipherError> {
-    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| CipherError::EncryptionFailed)?;
-
+pub fn encrypt_with(
+    algorithm: Algorithm,
+    key: &[u8; 32],
+    plaintext: &[u8],
+) -> Result<String, CipherError> {
     // Generate random nonce
     let mut nonce_bytes = [0u8; NONCE_SIZE];
     rand::thread_rng().fill_bytes(&mut nonce_bytes);
     let nonce = Nonce::from_slice(&nonce_bytes);
 
     // Encrypt
-    let ciphertext = cipher
-        .encrypt(nonce, plaintext)
-        .map_err(|_| CipherError::EncryptionFailed)?;
+    let ciphertext = match algorithm {
+        Algorithm::Aes256Gcm => Aes256Gcm::new_from_slice(key)
+            .map_err(|_| CipherError::EncryptionFailed)?
+            .encrypt(nonce, plaintext),
+        Algorithm::ChaCha20Poly1305 => ChaCha20Poly1305::new_from_slice(key)
+            .map_err(|_| CipherError::EncryptionFailed)?
+            .encrypt(nonce, plaintext),
+    }
+    .map_err(|_| CipherError::EncryptionFailed)?;
 
     // Combine nonce and ciphertext
     let mut combined = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
@@ -54,15 +137,20 @@ pub fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<String, CipherError>
     Ok(BASE64.encode(&combined))
 }
 
-/// Decrypts ciphertext using AES-256-GCM
+/// Decrypts ciphertext using the given algorithm
 ///
 /// # Arguments
+/// * `algorithm` - Cipher the data was encrypted with
 /// * `key` - 32-byte encryption key
 /// * `ciphertext_b64` - Base64-encoded ciphertext (nonce || ciphertext || tag)
 ///
 /// # Returns
 /// Decrypted plaintext bytes
-pub fn decrypt(key: &[u8; 32], ciphertext_b64: &str) -> Result<Vec<u8>, CipherError> {
+pub fn decrypt_with(
+    algorithm: Algorithm,
+    key: &[u8; 32],
+    ciphertext_b64: &str,
+) -> Result<Vec<u8>, CipherError> {
     let combined = BASE64
         .decode(ciphertext_b64)
         .map_err(|_| CipherError::InvalidFormat)?;
@@ -74,11 +162,189 @@ pub fn decrypt(key: &[u8; 32], ciphertext_b64: &str) -> Result<Vec<u8>, CipherEr
     let (nonce_bytes, ciphertext) = combined.split_at(NONCE_SIZE);
     let nonce = Nonce::from_slice(nonce_bytes);
 
-    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| CipherError::DecryptionFailed)?;
+    match algorithm {
+        Algorithm::Aes256Gcm => Aes256Gcm::new_from_slice(key)
+            .map_err(|_| CipherError::DecryptionFailed)?
+            .decrypt(nonce, ciphertext),
+        Algorithm::ChaCha20Poly1305 => ChaCha20Poly1305::new_from_slice(key)
+            .map_err(|_| CipherError::DecryptionFailed)?
+            .decrypt(nonce, ciphertext),
+    }
+    .map_err(|_| CipherError::DecryptionFailed)
+}
+
+/// Encrypts everything read from `reader` in chunks, writing to `writer`
+///
+/// Uses the STREAM construction: each chunk gets its own nonce derived from
+/// a random prefix, a counter and a last-chunk flag, so chunks cannot be
+/// reordered, dropped or truncated without decryption failing. Only one
+/// chunk is held in memory at a time.
+///
+/// Output: nonce prefix || chunk ciphertexts (each with its tag)
+pub fn encrypt_stream<R: Read, W: Write>(
+    algorithm: Algorithm,
+    key: &[u8; 32],
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<(), CipherError> {
+    match algorithm {
+        Algorithm::Aes256Gcm => {
+            let aead = Aes256Gcm::new_from_slice(key).map_err(|_| CipherError::EncryptionFailed)?;
+            encrypt_chunks(aead, reader, writer)
+        }
+        Algorithm::ChaCha20Poly1305 => {
+            let aead =
+                ChaCha20Poly1305::new_from_slice(key).map_err(|_| CipherError::EncryptionFailed)?;
+            encrypt_chunks(aead, reader, writer)
+        }
+    }
+}
+
+/// Decrypts output of `encrypt_stream`, writing the plaintext to `writer`
+///
+/// Plaintext of a chunk is only written once that chunk has authenticated,
+/// but earlier chunks may already have been written when a later one fails.
+pub fn decrypt_stream<R: Read, W: Write>(
+    algorithm: Algorithm,
+    key: &[u8; 32],
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<(), CipherError> {
+    match algorithm {
+        Algorithm::Aes256Gcm => {
+            let aead = Aes256Gcm::new_from_slice(key).map_err(|_| CipherError::DecryptionFailed)?;
+            decrypt_chunks(aead, reader, writer)
+        }
+        Algorithm::ChaCha20Poly1305 => {
+            let aead =
+                ChaCha20Poly1305::new_from_slice(key).map_err(|_| CipherError::DecryptionFailed)?;
+            decrypt_chunks(aead, reader, writer)
+        }
+    }
+}
+
+/// Encrypts an in-memory buffer in streaming mode, base64-encoded
+pub fn encrypt_chunked(
+    algorithm: Algorithm,
+    key: &[u8; 32],
+    plaintext: &[u8],
+) -> Result<String, CipherError> {
+    let mut encrypted =
+        Vec::with_capacity(plaintext.len() + plaintext.len() / STREAM_CHUNK_SIZE * TAG_SIZE + 64);
+    encrypt_stream(algorithm, key, &mut &plaintext[..], &mut encrypted)?;
+    Ok(BASE64.encode(&encrypted))
+}
+
+/// Decrypts base64 output of `encrypt_chunked`
+pub fn decrypt_chunked(
+    algorithm: Algorithm,
+    key: &[u8; 32],
+    ciphertext_b64: &str,
+) -> Result<Vec<u8>, CipherError> {
+    let encrypted = BASE64
+        .decode(ciphertext_b64)
+        .map_err(|_| CipherError::InvalidFormat)?;
+    let mut decrypted = Vec::with_capacity(encrypted.len());
+    decrypt_stream(algorithm, key, &mut encrypted.as_slice(), &mut decrypted)?;
+    Ok(decrypted)
+}
+
+fn encrypt_chunks<A, R, W>(aead: A, reader: &mut R, writer: &mut W) -> Result<(), CipherError>
+where
+    A: AeadInPlace + AeadCore<NonceSize = U12> + KeyInit,
+    R: Read,
+    W: Write,
+{
+    let mut prefix = [0u8; STREAM_NONCE_PREFIX_SIZE];
+    rand::thread_rng().fill_bytes(&mut prefix);
+    writer.write_all(&prefix)?;
+
+    let mut encryptor = EncryptorBE32::from_aead(aead, GenericArray::from_slice(&prefix));
+    let mut chunk = vec![0u8; STREAM_CHUNK_SIZE];
+    let mut len = read_full(reader, &mut chunk)?;
+
+    loop {
+        // Look one chunk ahead so the final chunk can be flagged as such
+        let mut next = vec![0u8; STREAM_CHUNK_SIZE];
+        let next_len = if len == STREAM_CHUNK_SIZE {
+            read_full(reader, &mut next)?
+        } else {
+            0
+        };
+
+        if next_len == 0 {
+            let ciphertext = encryptor
+                .encrypt_last(&chunk[..len])
+                .map_err(|_| CipherError::EncryptionFailed)?;
+            writer.write_all(&ciphertext)?;
+            return Ok(());
+        }
+
+        let ciphertext = encryptor
+            .encrypt_next(&chunk[..len])
+            .map_err(|_| CipherError::EncryptionFailed)?;
+        writer.write_all(&ciphertext)?;
+        chunk = next;
+        len = next_len;
+    }
+}
+
+fn decrypt_chunks<A, R, W>(aead: A, reader: &mut R, writer: &mut W) -> Result<(), CipherError>
+where
+    A: AeadInPlace + AeadCore<NonceSize = U12> + KeyInit,
+    R: Read,
+    W: Write,
+{
+    let mut prefix = [0u8; STREAM_NONCE_PREFIX_SIZE];
+    if read_full(reader, &mut prefix)? != STREAM_NONCE_PREFIX_SIZE {
+        return Err(CipherError::InvalidFormat);
+    }
+
+    let mut decryptor = DecryptorBE32::from_aead(aead, GenericArray::from_slice(&prefix));
+    let mut chunk = vec![0u8; STREAM_CHUNK_SIZE + TAG_SIZE];
+    let mut len = read_full(reader, &mut chunk)?;
+
+    loop {
+        if len < TAG_SIZE {
+            return Err(CipherError::InvalidFormat);
+        }
+
+        let mut next = vec![0u8; STREAM_CHUNK_SIZE + TAG_SIZE];
+        let next_len = if len == chunk.len() {
+            read_full(reader, &mut next)?
+        } else {
+            0
+        };
+
+        if next_len == 0 {
+            let plaintext = decryptor
+                .decrypt_last(&chunk[..len])
+                .map_err(|_| CipherError::DecryptionFailed)?;
+            writer.write_all(&plaintext)?;
+            return Ok(());
+        }
+
+        let plaintext = decryptor
+            .decrypt_next(&chunk[..len])
+            .map_err(|_| CipherError::DecryptionFailed)?;
+        writer.write_all(&plaintext)?;
+        chunk = next;
+        len = next_len;
+    }
+}
 
-    cipher
-        .decrypt(nonce, ciphertext)
-        .map_err(|_| CipherError::DecryptionFailed)
+/// Reads until `buf` is full or the reader is exhausted
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
 }
 
 #[cfg(test)]
@@ -119,4 +385,119 @@ mod tests {
         // Due to random nonce, each encryption should produce different output
         assert_ne!(encrypted1, encrypted2);
     }
+
+    #[test]
+    fn test_roundtrip_each_algorithm() {
+        let key = [0x42u8; 32];
+        let plaintext = b"Hello, World! This is a secret message.";
+
+        for algorithm in Algorithm::ALL {
+            let encrypted = encrypt_with(algorithm, &key, plaintext).unwrap();
+            let decrypted = decrypt_with(algorithm, &key, &encrypted).unwrap();
+            assert_eq!(plaintext.as_slice(), decrypted.as_slice(), "{}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_algorithm_mismatch_fails() {
+        let key = [0x42u8; 32];
+        let plaintext = b"Secret data";
+
+        let aes = encrypt_with(Algorithm::Aes256Gcm, &key, plaintext).unwrap();
+        assert!(decrypt_with(Algorithm::ChaCha20Poly1305, &key, &aes).is_err());
+
+        let chacha = encrypt_with(Algorithm::ChaCha20Poly1305, &key, plaintext).unwrap();
+        assert!(decrypt_with(Algorithm::Aes256Gcm, &key, &chacha).is_err());
+    }
+
+    #[test]
+    fn test_algorithm_ids() {
+        assert_eq!(Algorithm::default(), Algorithm::Aes256Gcm);
+        for algorithm in Algorithm::ALL {
+            assert_eq!(algorithm.id().parse::<Algorithm>().unwrap(), algorithm);
+            assert_eq!(
+                serde_json::to_string(&algorithm).unwrap(),
+                format!("\"{}\"", algorithm.id())
+            );
+        }
+        assert!("des".parse::<Algorithm>().is_err());
+    }
+
+    fn stream_roundtrip(algorithm: Algorithm, plaintext: &[u8]) -> Vec<u8> {
+        let key = [0x42u8; 32];
+        let mut encrypted = Vec::new();
+        encrypt_stream(algorithm, &key, &mut &plaintext[..], &mut encrypted).unwrap();
+
+        let mut decrypted = Vec::new();
+        decrypt_stream(algorithm, &key, &mut encrypted.as_slice(), &mut decrypted).unwrap();
+        decrypted
+    }
+
+    #[test]
+    fn test_stream_roundtrip_multi_megabyte() {
+        let mut plaintext = vec![0u8; 3 * 1024 * 1024 + 123];
+        rand::thread_rng().fill_bytes(&mut plaintext);
+
+        for algorithm in Algorithm::ALL {
+            assert!(
+                stream_roundtrip(algorithm, &plaintext) == plaintext,
+                "{}",
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_roundtrip_chunk_boundaries() {
+        for len in [
+            0,
+            1,
+            STREAM_CHUNK_SIZE,
+            STREAM_CHUNK_SIZE + 1,
+            2 * STREAM_CHUNK_SIZE,
+        ] {
+            let plaintext = vec![0x5au8; len];
+            assert_eq!(
+                stream_roundtrip(Algorithm::default(), &plaintext),
+                plaintext,
+                "{}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_rejects_tampering() {
+        let key = [0x42u8; 32];
+        let plaintext = vec![0x5au8; 2 * STREAM_CHUNK_SIZE + 10];
+        let mut encrypted = Vec::new();
+        encrypt_stream(
+            Algorithm::default(),
+            &key,
+            &mut plaintext.as_slice(),
+            &mut encrypted,
+        )
+        .unwrap();
+
+        let decrypt = |data: &[u8]| {
+            decrypt_stream(Algorithm::default(), &key, &mut &data[..], &mut Vec::new())
+        };
+
+        // Dropping the final chunk must not look like a shorter valid stream
+        let truncated = &encrypted[..STREAM_NONCE_PREFIX_SIZE + 2 * (STREAM_CHUNK_SIZE + TAG_SIZE)];
+        assert!(decrypt(truncated).is_err());
+
+        let mut flipped = encrypted.clone();
+        flipped[STREAM_NONCE_PREFIX_SIZE + 5] ^= 1;
+        assert!(decrypt(&flipped).is_err());
+
+        assert!(decrypt(&encrypted).is_ok());
+        assert!(decrypt_stream(
+            Algorithm::ChaCha20Poly1305,
+            &key,
+            &mut encrypted.as_slice(),
+            &mut Vec::new()
+        )
+        .is_err());
+    }
 }
@@ -8,8 +8,12 @@ use argon2::{
     password_hash::{PasswordHasher, SaltString},
     Argon2, Params, Version,
 };
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
 use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
 
 /// Key derivation errors
 #[derive(Error, Debug)]
@@ -18,6 +22,8 @@ pub enum KdfError {
     DerivationError(String),
     #[error("Invalid salt")]
     InvalidSalt,
+    #[error("KDF parameters out of range: {0}")]
+    ParamsOutOfRange(String),
 }
 
 /// Argon2id parameters for key derivation
@@ -30,6 +36,70 @@ const TIME_COST: u32 = 3;
 const PARALLELISM: u32 = 4;
 const OUTPUT_LEN: usize = 32;
 
+/// Upper bounds on the parameters a vault file may ask for: 4 GiB of memory,
+/// 64 passes and 64 lanes. The parameters sit in the unauthenticated
+/// envelope, so without a cap a tampered file could hang the machine.
+pub const MAX_MEMORY_COST: u32 = 4 * 1024 * 1024;
+pub const MAX_ITERATIONS: u32 = 64;
+pub const MAX_PARALLELISM: u32 = 64;
+
+/// Bytes of randomness in a salt from `generate_salt`
+pub const SALT_LEN: usize = 16;
+
+/// How salts are written in vault files
+pub const SALT_ENCODING: &str = "base64 (unpadded)";
+
+/// Tunable Argon2id cost parameters
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub memory_cost: u32,
+    /// Number of passes over memory
+    pub iterations: u32,
+    /// Degree of parallelism (lanes)
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// The fixed parameters used by version 1 vault files
+    pub const LEGACY: KdfParams = KdfParams {
+        memory_cost: MEMORY_COST,
+        iterations: TIME_COST,
+        parallelism: PARALLELISM,
+    };
+
+    /// Fails if any parameter exceeds its `MAX_*` bound
+    pub fn check_bounds(&self) -> Result<(), KdfError> {
+        let out_of_range = |name: &str, value: u32, max: u32| {
+            KdfError::ParamsOutOfRange(format!("{} {} exceeds {}", name, value, max))
+        };
+        if self.memory_cost > MAX_MEMORY_COST {
+            return Err(out_of_range(
+                "memory cost",
+                self.memory_cost,
+                MAX_MEMORY_COST,
+            ));
+        }
+        if self.iterations > MAX_ITERATIONS {
+            return Err(out_of_range("iterations", self.iterations, MAX_ITERATIONS));
+        }
+        if self.parallelism > MAX_PARALLELISM {
+            return Err(out_of_range(
+                "parallelism",
+                self.parallelism,
+                MAX_PARALLELISM,
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::LEGACY
+    }
+}
+
 /// Derives a 256-bit encryption key from a password using Argon2id
 ///
 /// # Arguments
@@ -39,10 +109,27 @@ const OUTPUT_LEN: usize = 32;
 /// # Returns
 /// A 32-byte (256-bit) key suitable for AES-256-GCM
 pub fn derive_key(password: &str, salt: &str) -> Result<[u8; 32], KdfError> {
+    derive_key_with_params(password, salt, KdfParams::default())
+}
+
+/// Derives a 256-bit encryption key using the given Argon2id parameters
+///
+/// Parameters beyond the `MAX_*` bounds are rejected before any work is done.
+pub fn derive_key_with_params(
+    password: &str,
+    salt: &str,
+    params: KdfParams,
+) -> Result<[u8; 32], KdfError> {
+    params.check_bounds()?;
     let salt = SaltString::from_b64(salt).map_err(|_| KdfError::InvalidSalt)?;
 
-    let params = Params::new(MEMORY_COST, TIME_COST, PARALLELISM, Some(OUTPUT_LEN))
-        .map_err(|e| KdfError::DerivationError(e.to_string()))?;
+    let params = Params::new(
+        params.memory_cost,
+        params.iterations,
+        params.parallelism,
+        Some(OUTPUT_LEN),
+    )
+    .map_err(|e| KdfError::DerivationError(e.to_string()))?;
 
     let argon2 = Argon2::new(argon2::Algorithm::Argon2id, Version::V0x13, params);
 
@@ -59,6 +146,16 @@ pub fn derive_key(password: &str, salt: &str) -> Result<[u8; 32], KdfError> {
     Ok(key)
 }
 
+/// Puts a password into Unicode NFKC form before key derivation
+///
+/// The same passphrase can arrive composed ("é") or decomposed ("e" plus a
+/// combining accent) depending on the platform and input method, and would
+/// otherwise derive different keys. Vault master passwords are normalized
+/// from vault format 5 on.
+pub fn normalize_password(password: &str) -> Zeroizing<String> {
+    Zeroizing::new(password.nfkc().collect())
+}
+
 /// Generates a new random salt for key derivation
 ///
 /// # Returns
@@ -67,6 +164,13 @@ pub fn generate_salt() -> String {
     SaltString::generate(&mut OsRng).to_string()
 }
 
+/// Decodes a salt written by `generate_salt` into its raw bytes
+pub fn decode_salt(salt: &str) -> Result<Vec<u8>, KdfError> {
+    STANDARD_NO_PAD
+        .decode(salt)
+        .map_err(|_| KdfError::InvalidSalt)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,30 +179,108 @@ mod tests {
     fn test_key_derivation_consistency() {
         let password = "test_password_123";
         let salt = generate_salt();
-        
+
         let key1 = derive_key(password, &salt).unwrap();
         let key2 = derive_key(password, &salt).unwrap();
-        
+
         assert_eq!(key1, key2, "Same password and salt should produce same key");
     }
 
     #[test]
     fn test_different_passwords_different_keys() {
         let salt = generate_salt();
-        
+
         let key1 = derive_key("password1", &salt).unwrap();
         let key2 = derive_key("password2", &salt).unwrap();
-        
-        assert_ne!(key1, key2, "Different passwords should produce different keys");
+
+        assert_ne!(
+            key1, key2,
+            "Different passwords should produce different keys"
+        );
     }
 
     #[test]
     fn test_different_salts_different_keys() {
         let password = "same_password";
-        
+
         let key1 = derive_key(password, &generate_salt()).unwrap();
         let key2 = derive_key(password, &generate_salt()).unwrap();
-        
+
         assert_ne!(key1, key2, "Different salts should produce different keys");
     }
+
+    #[test]
+    fn test_params_change_the_key() {
+        let salt = generate_salt();
+        let light = KdfParams {
+            memory_cost: 8192,
+            iterations: 1,
+            parallelism: 1,
+        };
+
+        let key1 = derive_key_with_params("password", &salt, light).unwrap();
+        let key2 = derive_key_with_params("password", &salt, light).unwrap();
+        assert_eq!(key1, key2);
+        assert_ne!(key1, derive_key("password", &salt).unwrap());
+
+        let invalid = KdfParams {
+            iterations: 0,
+            ..light
+        };
+        assert!(matches!(
+            derive_key_with_params("password", &salt, invalid),
+            Err(KdfError::DerivationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_params_beyond_bounds_are_rejected() {
+        let salt = generate_salt();
+        let max = KdfParams {
+            memory_cost: MAX_MEMORY_COST,
+            iterations: MAX_ITERATIONS,
+            parallelism: MAX_PARALLELISM,
+        };
+        assert!(max.check_bounds().is_ok());
+
+        for params in [
+            KdfParams {
+                memory_cost: u32::MAX,
+                ..max
+            },
+            KdfParams {
+                iterations: MAX_ITERATIONS + 1,
+                ..max
+            },
+            KdfParams {
+                parallelism: MAX_PARALLELISM + 1,
+                ..max
+            },
+        ] {
+            assert!(matches!(
+                derive_key_with_params("password", &salt, params),
+                Err(KdfError::ParamsOutOfRange(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_generated_salt_decodes_to_salt_len() {
+        assert_eq!(decode_salt(&generate_salt()).unwrap().len(), SALT_LEN);
+        assert!(matches!(
+            decode_salt("not base64!"),
+            Err(KdfError::InvalidSalt)
+        ));
+    }
+
+    #[test]
+    fn test_normalize_password_unifies_composed_and_decomposed() {
+        let composed = "caf\u{e9} cr\u{e8}me";
+        let decomposed = "cafe\u{301} cre\u{300}me";
+        assert_ne!(composed, decomposed);
+        assert_eq!(*normalize_password(composed), composed);
+        assert_eq!(*normalize_password(decomposed), composed);
+        // Compatibility forms fold too: full-width letters, ligatures
+        assert_eq!(*normalize_password("\u{ff21}\u{fb01}"), "Afi");
+    }
 }
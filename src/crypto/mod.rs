@@ -2,5 +2,77 @@
 
 pub mod cipher;
 pub mod kdf;
+pub mod totp;
 
-pub use cipher::{decrypt, encrypt};
+pub use cipher::{
+    decrypt, decrypt_chunked, decrypt_with, encrypt, encrypt_chunked, encrypt_with, Algorithm,
+};
+
+use subtle::ConstantTimeEq;
+
+/// Compares two byte strings in time independent of their contents
+///
+/// Use this for secret values instead of `==`, which returns at the first
+/// differing byte. Only the lengths can be told apart by timing.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"", b""));
+        assert!(constant_time_eq(b"hunter2", b"hunter2"));
+        assert!(!constant_time_eq(b"hunter2", b"hunter3"));
+        assert!(!constant_time_eq(b"hunter2", b"Hunter2"));
+        assert!(!constant_time_eq(b"hunter2", b"hunter22"));
+        assert!(!constant_time_eq(b"", b"x"));
+    }
+
+    /// Times matching inputs against inputs differing in the first or last byte
+    ///
+    /// Timing-sensitive, so ignored by default:
+    /// `cargo test --release constant_time_eq_timing -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn test_constant_time_eq_timing() {
+        const ROUNDS: usize = 20_000;
+        let secret = vec![0x5a_u8; 4096];
+        let mut first = secret.clone();
+        first[0] ^= 1;
+        let mut last = secret.clone();
+        *last.last_mut().unwrap() ^= 1;
+
+        let time = |other: &[u8]| {
+            // Best of several runs to filter out scheduler noise
+            (0..5)
+                .map(|_| {
+                    let start = Instant::now();
+                    for _ in 0..ROUNDS {
+                        black_box(constant_time_eq(black_box(&secret), black_box(other)));
+                    }
+                    start.elapsed()
+                })
+                .min()
+                .unwrap()
+        };
+        let timings: Vec<Duration> = [&secret, &first, &last].map(|other| time(other)).into();
+        println!(
+            "match {:?}, first byte differs {:?}, last byte differs {:?}",
+            timings[0], timings[1], timings[2]
+        );
+
+        let fastest = *timings.iter().min().unwrap();
+        let slowest = *timings.iter().max().unwrap();
+        assert!(
+            slowest.as_secs_f64() < fastest.as_secs_f64() * 1.5,
+            "timings diverge: {:?}",
+            timings
+        );
+    }
+}
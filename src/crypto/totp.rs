@@ -0,0 +1,234 @@
+//! Time-based one-time passwords (RFC 6238)
+//!
+//! TOTP computes an HOTP value (RFC 4226) over the number of `period`-second
+//! steps elapsed since the Unix epoch.
+
+use data_encoding::BASE32_NOPAD;
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// TOTP errors
+#[derive(Error, Debug)]
+pub enum TotpError {
+    #[error("Invalid base32 secret")]
+    InvalidSecret,
+    #[error("Invalid character '{0}' in base32 secret (allowed: A-Z and 2-7)")]
+    InvalidSecretChar(char),
+    #[error("Invalid number of digits: {0} (expected 6-10)")]
+    InvalidDigits(u32),
+    #[error("Invalid period: must be greater than zero")]
+    InvalidPeriod,
+}
+
+/// HMAC algorithm used to compute codes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TotpAlgorithm {
+    #[default]
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl std::fmt::Display for TotpAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TotpAlgorithm::Sha1 => write!(f, "SHA1"),
+            TotpAlgorithm::Sha256 => write!(f, "SHA256"),
+            TotpAlgorithm::Sha512 => write!(f, "SHA512"),
+        }
+    }
+}
+
+/// Default number of digits in a code
+pub const DEFAULT_DIGITS: u32 = 6;
+
+/// Default time step in seconds
+pub const DEFAULT_PERIOD: u64 = 30;
+
+/// Validates a user-supplied base32 seed and returns it in canonical form
+///
+/// Spaces, lowercase letters and `=` padding are accepted, as authenticator
+/// setup pages often show seeds as `jbsw y3dp ehpk 3pxp`. The result is
+/// unpadded upper-case base32 that `decode_secret` accepts.
+pub fn normalize_base32_secret(secret: &str) -> Result<String, TotpError> {
+    let normalized: String = secret
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let normalized = normalized.trim_end_matches('=');
+
+    if let Some(c) = normalized
+        .chars()
+        .find(|c| !matches!(c, 'A'..='Z' | '2'..='7'))
+    {
+        return Err(TotpError::InvalidSecretChar(c));
+    }
+    decode_secret(normalized)?;
+    Ok(normalized.to_string())
+}
+
+/// Validates and decodes a user-supplied base32 seed (see `normalize_base32_secret`)
+pub fn parse_base32_secret(secret: &str) -> Result<Vec<u8>, TotpError> {
+    decode_secret(&normalize_base32_secret(secret)?)
+}
+
+/// Decodes an unpadded, uppercase base32 secret
+pub fn decode_secret(secret: &str) -> Result<Vec<u8>, TotpError> {
+    let trimmed = secret.trim_end_matches('=');
+    let decoded = BASE32_NOPAD
+        .decode(trimmed.as_bytes())
+        .map_err(|_| TotpError::InvalidSecret)?;
+
+    if decoded.is_empty() {
+        return Err(TotpError::InvalidSecret);
+    }
+
+    Ok(decoded)
+}
+
+/// Computes an HOTP code for the given counter
+pub fn hotp(
+    key: &[u8],
+    counter: u64,
+    algorithm: TotpAlgorithm,
+    digits: u32,
+) -> Result<String, TotpError> {
+    if !(6..=10).contains(&digits) {
+        return Err(TotpError::InvalidDigits(digits));
+    }
+
+    let message = counter.to_be_bytes();
+    let digest = match algorithm {
+        TotpAlgorithm::Sha1 => hmac_digest::<Hmac<sha1::Sha1>>(key, &message),
+        TotpAlgorithm::Sha256 => hmac_digest::<Hmac<sha2::Sha256>>(key, &message),
+        TotpAlgorithm::Sha512 => hmac_digest::<Hmac<sha2::Sha512>>(key, &message),
+    };
+
+    // Dynamic truncation (RFC 4226 section 5.3)
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+
+    let code = u64::from(binary) % 10u64.pow(digits);
+    Ok(format!("{:0width$}", code, width = digits as usize))
+}
+
+/// Computes the TOTP code for a Unix timestamp
+pub fn totp(
+    key: &[u8],
+    unix_time: u64,
+    period: u64,
+    algorithm: TotpAlgorithm,
+    digits: u32,
+) -> Result<String, TotpError> {
+    if period == 0 {
+        return Err(TotpError::InvalidPeriod);
+    }
+    hotp(key, unix_time / period, algorithm, digits)
+}
+
+fn hmac_digest<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vectors from RFC 6238 appendix B
+    const SEED_SHA1: &[u8] = b"12345678901234567890";
+    const SEED_SHA256: &[u8] = b"12345678901234567890123456789012";
+    const SEED_SHA512: &[u8] = b"1234567890123456789012345678901234567890123456789012345678901234";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        let cases = [
+            (59, "94287082", "46119246", "90693936"),
+            (1111111109, "07081804", "68084774", "25091201"),
+            (1234567890, "89005924", "91819424", "93441116"),
+            (20000000000, "65353130", "77737706", "47863826"),
+        ];
+
+        for (time, sha1, sha256, sha512) in cases {
+            assert_eq!(
+                totp(SEED_SHA1, time, 30, TotpAlgorithm::Sha1, 8).unwrap(),
+                sha1
+            );
+            assert_eq!(
+                totp(SEED_SHA256, time, 30, TotpAlgorithm::Sha256, 8).unwrap(),
+                sha256
+            );
+            assert_eq!(
+                totp(SEED_SHA512, time, 30, TotpAlgorithm::Sha512, 8).unwrap(),
+                sha512
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_secret() {
+        // "12345678901234567890" in base32
+        let decoded = decode_secret("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ").unwrap();
+        assert_eq!(decoded, SEED_SHA1);
+
+        assert!(matches!(
+            decode_secret("not base32!"),
+            Err(TotpError::InvalidSecret)
+        ));
+        assert!(matches!(decode_secret(""), Err(TotpError::InvalidSecret)));
+    }
+
+    #[test]
+    fn test_parse_base32_secret_tolerates_formatting() {
+        let spaced = "gezd gnbv gy3t qojq GEZD GNBV GY3T QOJQ";
+        assert_eq!(parse_base32_secret(spaced).unwrap(), SEED_SHA1);
+        assert_eq!(
+            normalize_base32_secret("jbsw y3dp ehpk 3pxp").unwrap(),
+            "JBSWY3DPEHPK3PXP"
+        );
+        // Padded and unpadded forms decode the same
+        assert_eq!(
+            parse_base32_secret("MZXW6===").unwrap(),
+            parse_base32_secret("mzxw6").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_base32_secret_rejects_garbage() {
+        assert!(matches!(
+            parse_base32_secret("JBSW Y3DP 1HPK"),
+            Err(TotpError::InvalidSecretChar('1'))
+        ));
+        assert!(matches!(
+            parse_base32_secret("   "),
+            Err(TotpError::InvalidSecret)
+        ));
+        // Valid characters but an impossible length
+        assert!(matches!(
+            parse_base32_secret("ABC"),
+            Err(TotpError::InvalidSecret)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_digits_and_period() {
+        assert!(matches!(
+            hotp(SEED_SHA1, 0, TotpAlgorithm::Sha1, 4),
+            Err(TotpError::InvalidDigits(4))
+        ));
+        assert!(matches!(
+            totp(SEED_SHA1, 0, 0, TotpAlgorithm::Sha1, 6),
+            Err(TotpError::InvalidPeriod)
+        ));
+    }
+}
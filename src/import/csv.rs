@@ -0,0 +1,166 @@
+//! Generic CSV import with a caller-supplied column mapping
+//!
+//! For exports whose headers don't match any known tool, `ColumnMapping`
+//! names the CSV header that feeds each `Password` field. Headers are matched
+//! case-insensitively, and columns that aren't mapped are ignored.
+
+use super::{read_csv, ImportError};
+use crate::vault::types::Password;
+use std::io::Read;
+
+/// CSV header for each `Password` field; `name` and `password` are required
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMapping {
+    pub name: Option<String>,
+    pub password: Option<String>,
+    pub username: Option<String>,
+    pub url: Option<String>,
+    pub description: Option<String>,
+}
+
+impl ColumnMapping {
+    /// Required fields that have no column assigned
+    pub fn unmapped_required(&self) -> Vec<&'static str> {
+        [("name", &self.name), ("password", &self.password)]
+            .into_iter()
+            .filter(|(_, header)| header.is_none())
+            .map(|(field, _)| field)
+            .collect()
+    }
+}
+
+/// Parses CSV into passwords using `mapping` to locate each field
+///
+/// Fails with `UnmappedFields` if `name` or `password` has no column, and
+/// with `ColumnNotFound` if a mapped header isn't in the file. Empty optional
+/// fields are stored as `None`.
+pub fn import_with_mapping(
+    reader: impl Read,
+    mapping: &ColumnMapping,
+) -> Result<Vec<Password>, ImportError> {
+    let unmapped = mapping.unmapped_required();
+    if !unmapped.is_empty() {
+        return Err(ImportError::UnmappedFields(unmapped));
+    }
+
+    let mut rows = read_csv(reader)?.into_iter();
+    let headers = rows.next().unwrap_or_default();
+
+    let column = |header: &Option<String>| -> Result<Option<usize>, ImportError> {
+        let Some(header) = header else {
+            return Ok(None);
+        };
+        headers
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(header.trim()))
+            .map(Some)
+            .ok_or_else(|| ImportError::ColumnNotFound(header.clone()))
+    };
+    let name = column(&mapping.name)?;
+    let password = column(&mapping.password)?;
+    let username = column(&mapping.username)?;
+    let url = column(&mapping.url)?;
+    let description = column(&mapping.description)?;
+
+    let passwords = rows
+        .map(|record| {
+            let field = |idx: Option<usize>| {
+                idx.and_then(|i| record.get(i))
+                    .filter(|v| !v.is_empty())
+                    .cloned()
+            };
+
+            Password::new(
+                field(name).unwrap_or_default(),
+                field(password).unwrap_or_default(),
+                field(description),
+                field(username),
+                field(url),
+            )
+        })
+        .collect();
+
+    Ok(passwords)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\
+Site Label,Login,Secret,Link,Comment,Folder
+github,octocat,hunter2,https://github.com,Personal account,dev
+\"wifi, home\",,\"p\"\"ss\",,,home
+";
+
+    fn mapping() -> ColumnMapping {
+        ColumnMapping {
+            name: Some("Site Label".into()),
+            password: Some("secret".into()),
+            username: Some("Login".into()),
+            url: Some("Link".into()),
+            description: Some("Comment".into()),
+        }
+    }
+
+    #[test]
+    fn test_import_with_custom_mapping() {
+        let passwords = import_with_mapping(CSV.as_bytes(), &mapping()).unwrap();
+        assert_eq!(passwords.len(), 2);
+
+        let github = &passwords[0];
+        assert_eq!(github.name, "github");
+        assert_eq!(github.password.expose_secret(), "hunter2");
+        assert_eq!(github.username.as_deref(), Some("octocat"));
+        assert_eq!(github.url.as_deref(), Some("https://github.com"));
+        assert_eq!(github.description.as_deref(), Some("Personal account"));
+
+        let wifi = &passwords[1];
+        assert_eq!(wifi.name, "wifi, home");
+        assert_eq!(wifi.password.expose_secret(), "p\"ss");
+        assert_eq!(wifi.username, None);
+        assert_eq!(wifi.description, None);
+    }
+
+    #[test]
+    fn test_optional_fields_can_be_left_unmapped() {
+        let mapping = ColumnMapping {
+            name: Some("Site Label".into()),
+            password: Some("Secret".into()),
+            ..Default::default()
+        };
+        let passwords = import_with_mapping(CSV.as_bytes(), &mapping).unwrap();
+        assert_eq!(passwords[0].username, None);
+        assert_eq!(passwords[0].url, None);
+    }
+
+    #[test]
+    fn test_unmapped_required_fields_are_listed() {
+        let err = import_with_mapping(CSV.as_bytes(), &ColumnMapping::default()).unwrap_err();
+        assert!(
+            matches!(&err, ImportError::UnmappedFields(fields) if fields == &["name", "password"])
+        );
+
+        let mapping = ColumnMapping {
+            name: Some("Site Label".into()),
+            ..Default::default()
+        };
+        let err = import_with_mapping(CSV.as_bytes(), &mapping).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No column mapped for required fields: password"
+        );
+    }
+
+    #[test]
+    fn test_mapped_header_must_exist() {
+        let mapping = ColumnMapping {
+            url: Some("Website".into()),
+            ..mapping()
+        };
+        assert!(matches!(
+            import_with_mapping(CSV.as_bytes(), &mapping),
+            Err(ImportError::ColumnNotFound(header)) if header == "Website"
+        ));
+    }
+}
@@ -0,0 +1,108 @@
+//! KeePass CSV import
+//!
+//! Reads the CSV produced by KeePass's "Export to CSV", which has the columns
+//! `Title`, `Username`, `Password`, `URL` and `Notes`. Columns are located by
+//! their header name, so extra columns (e.g. `Group`) and reordering are fine.
+
+use super::{read_csv, ImportError};
+use crate::vault::types::Password;
+use std::io::Read;
+
+/// Parses a KeePass CSV export into passwords
+///
+/// The Notes column becomes the password's description. Empty optional
+/// fields are stored as `None`.
+pub fn parse_keepass_csv(reader: impl Read) -> Result<Vec<Password>, ImportError> {
+    let mut rows = read_csv(reader)?.into_iter();
+    let headers = rows.next().unwrap_or_default();
+
+    let column = |name: &'static str| {
+        headers
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+            .ok_or(ImportError::MissingColumn(name))
+    };
+    let title = column("Title")?;
+    let password = column("Password")?;
+    let username = column("Username").ok();
+    let url = column("URL").ok();
+    let notes = column("Notes").ok();
+
+    let passwords = rows
+        .map(|record| {
+            let field = |idx: Option<usize>| {
+                idx.and_then(|i| record.get(i))
+                    .filter(|v| !v.is_empty())
+                    .cloned()
+            };
+
+            Password::new(
+                record.get(title).cloned().unwrap_or_default(),
+                record.get(password).cloned().unwrap_or_default(),
+                field(notes),
+                field(username),
+                field(url),
+            )
+        })
+        .collect();
+
+    Ok(passwords)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use crate::vault::transfer::ImportMode;
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/keepass.csv");
+
+    #[test]
+    fn test_parse_keepass_fixture() {
+        let passwords = parse_keepass_csv(FIXTURE.as_bytes()).unwrap();
+        assert_eq!(passwords.len(), 3);
+
+        let github = &passwords[0];
+        assert_eq!(github.name, "GitHub");
+        assert_eq!(github.username.as_deref(), Some("octocat"));
+        assert_eq!(github.password.expose_secret(), "hunter2");
+        assert_eq!(github.url.as_deref(), Some("https://github.com"));
+        assert_eq!(github.description.as_deref(), Some("Personal account"));
+
+        let bank = &passwords[1];
+        assert_eq!(bank.name, "Bank, checking");
+        assert_eq!(bank.password.expose_secret(), "p\"ss,word");
+        assert_eq!(
+            bank.description.as_deref(),
+            Some("Security questions:\nfirst pet: rex\ncity: springfield")
+        );
+
+        let wifi = &passwords[2];
+        assert_eq!(wifi.username, None);
+        assert_eq!(wifi.url, None);
+        assert_eq!(wifi.description, None);
+    }
+
+    #[test]
+    fn test_import_into_vault() {
+        let (_dir, mut vault) = test_util::test_vault();
+
+        let passwords = parse_keepass_csv(FIXTURE.as_bytes()).unwrap();
+        let report = vault.import_passwords(passwords, ImportMode::Skip).unwrap();
+        assert_eq!(report.added, 3);
+
+        let passwords = parse_keepass_csv(FIXTURE.as_bytes()).unwrap();
+        let report = vault.import_passwords(passwords, ImportMode::Skip).unwrap();
+        assert_eq!(report.skipped, 3);
+        assert_eq!(vault.list_passwords().len(), 3);
+    }
+
+    #[test]
+    fn test_missing_password_column() {
+        let csv = "\"Title\",\"Username\"\n\"a\",\"b\"\n";
+        assert!(matches!(
+            parse_keepass_csv(csv.as_bytes()),
+            Err(ImportError::MissingColumn("Password"))
+        ));
+    }
+}
@@ -0,0 +1,110 @@
+//! Importers for other password managers' export formats
+//!
+//! Each importer parses an export into kookie secret types. The results are
+//! handed to the vault (e.g. `Vault::import_passwords`) which resolves name
+//! collisions according to an `ImportMode`.
+
+pub mod bitwarden;
+pub mod csv;
+pub mod keepass;
+
+use std::io::Read;
+use thiserror::Error;
+
+/// Import errors
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Invalid JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+    #[error("Encrypted exports are not supported; export as unencrypted JSON")]
+    EncryptedExport,
+    #[error("Invalid CSV: {0}")]
+    InvalidCsv(String),
+    #[error("Missing required column: {0}")]
+    MissingColumn(&'static str),
+    #[error("No column mapped for required fields: {}", .0.join(", "))]
+    UnmappedFields(Vec<&'static str>),
+    #[error("Column not found: {0}")]
+    ColumnNotFound(String),
+}
+
+/// Reads RFC 4180 style CSV into rows of fields
+///
+/// Quoted fields may contain commas, newlines and doubled quotes (`""`).
+/// Blank lines are skipped.
+pub(crate) fn read_csv(mut reader: impl Read) -> Result<Vec<Vec<String>>, ImportError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    let input = input.strip_prefix('\u{feff}').unwrap_or(&input);
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                push_row(&mut rows, std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(ImportError::InvalidCsv("unterminated quoted field".into()));
+    }
+    row.push(field);
+    push_row(&mut rows, row);
+
+    Ok(rows)
+}
+
+fn push_row(rows: &mut Vec<Vec<String>>, row: Vec<String>) {
+    let blank = row.len() == 1 && row[0].is_empty();
+    if !blank {
+        rows.push(row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_csv_quoting() {
+        let input = "a,\"b,c\",\"say \"\"hi\"\"\"\r\n\n\"multi\nline\",,x\n";
+        let rows = read_csv(input.as_bytes()).unwrap();
+        assert_eq!(
+            rows,
+            vec![vec!["a", "b,c", "say \"hi\""], vec!["multi\nline", "", "x"],]
+        );
+    }
+
+    #[test]
+    fn test_read_csv_unterminated_quote() {
+        assert!(matches!(
+            read_csv("a,\"b\n".as_bytes()),
+            Err(ImportError::InvalidCsv(_))
+        ));
+    }
+}
@@ -3,8 +3,11 @@
 //! This library provides the core functionality for managing encrypted secrets
 //! including passwords, API keys, notes, database credentials, and tokens.
 
+pub mod audit;
 pub mod commands;
+pub mod config;
 pub mod crypto;
+pub mod import;
 pub mod session;
 pub mod utils;
 pub mod vault;
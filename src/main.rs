@@ -5,47 +5,87 @@
 //! ```bash
 //! # Initialize vault
 //! kookie init
+//! kookie init --cipher chacha20-poly1305
 //!
 //! # Add secrets
 //! kookie add --password
+//! kookie add --password --generate
 //! kookie add --api-key
 //! kookie add --note
 //! kookie add --db
 //! kookie add --token
+//! kookie add --totp
+//! kookie add --ssh-key
+//! kookie add --card
+//! kookie add --wifi
+//! kookie add --template stripe
 //!
 //! # List secrets
 //! kookie list
 //! kookie list --passwords
+//! kookie list --sort created --desc
 //!
 //! # Get a secret
 //! kookie get <name-or-id>
+//! kookie get <name-or-id> --json [--reveal]
+//! kookie get <name-or-id> --raw
 //! kookie get <name-or-id> --copy
+//! kookie get <name-or-id> --qr
+//! kookie otp <name> --copy
 //!
-//! # Delete a secret
+//! # Search secrets
+//! kookie search <query>
+//! kookie search <query> --type password,api-key --limit 5
+//!
+//! # Delete a secret (moves it to the trash)
 //! kookie delete <name-or-id>
+//! kookie delete --tag <tag>
+//! kookie trash
+//! kookie trash --restore <id>
 //!
 //! # Lock/unlock
 //! kookie lock
 //! kookie unlock
+//! kookie change-password
+//! kookie recovery-key
+//! kookie recover
 //!
 //! # Generate secrets
 //! kookie generate jwt
 //! kookie generate key --length 32
 //! kookie generate password --length 16
+//! kookie generate passphrase --words 6
+//! kookie generate pronounceable --syllables 5 --capitalize --digits 2
+//! kookie generate pattern LLdd-ssss
+//!
+//! # Backup and restore
+//! kookie export backup.kookie
+//! kookie export team.kookie --only db-credential
+//! kookie import backup.kookie --mode rename
+//! kookie import backup.kookie --dry-run
+//!
+//! # Check vault health
+//! kookie doctor
+//! kookie stats
+//! kookie history --limit 20
+//! kookie diff vault-a.json vault-b.json
+//! kookie info
 //!
 //! # Configure
 //! kookie config --timeout 10
 //! kookie config --show
+//! kookie config --lockout 5 --lockout-minutes 30
+//! kookie config --min-length 14 --require-digit --forbid-common
 //! ```
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
-
-mod commands;
-mod crypto;
-mod session;
-mod utils;
-mod vault;
+use kookie::commands;
+use kookie::config::Config;
+use kookie::vault::transfer::ImportMode;
+use kookie::vault::policy::PasswordPolicy;
+use kookie::vault::types::{SecretType, SortKey};
+use std::path::PathBuf;
 
 /// 🍪 Kookie - A secure, local-first, encrypted secret manager for developers
 #[derive(Parser)]
@@ -64,6 +104,10 @@ enum Commands {
         /// Force reinitialization (deletes existing vault)
         #[arg(short, long)]
         force: bool,
+        
+        /// Cipher to encrypt with (aes-256-gcm or chacha20-poly1305)
+        #[arg(long, value_name = "ALGORITHM")]
+        cipher: Option<String>,
     },
     
     /// Lock the vault (clear session)
@@ -76,6 +120,17 @@ enum Commands {
         timeout: Option<u32>,
     },
     
+    /// Change the master password
+    #[command(name = "change-password")]
+    ChangePassword,
+    
+    /// Create a recovery key that can reset a forgotten master password
+    #[command(name = "recovery-key")]
+    RecoveryKey,
+    
+    /// Set a new master password using the recovery key
+    Recover,
+    
     /// Add a new secret
     Add {
         /// Add a password
@@ -97,6 +152,30 @@ enum Commands {
         /// Add a token
         #[arg(long, group = "secret_type")]
         token: bool,
+        
+        /// Add a TOTP (2FA) secret
+        #[arg(long, group = "secret_type")]
+        totp: bool,
+        
+        /// Add an SSH key pair
+        #[arg(long, group = "secret_type")]
+        ssh_key: bool,
+        
+        /// Add a credit card
+        #[arg(long, group = "secret_type")]
+        card: bool,
+        
+        /// Add a WiFi network
+        #[arg(long, group = "secret_type")]
+        wifi: bool,
+        
+        /// Add a secret prefilled for a common service
+        #[arg(long, value_enum, group = "secret_type")]
+        template: Option<TemplateArg>,
+        
+        /// Generate the password or API key instead of entering it
+        #[arg(short, long)]
+        generate: bool,
     },
     
     /// List stored secrets
@@ -120,6 +199,30 @@ enum Commands {
         /// Show only tokens
         #[arg(long)]
         tokens: bool,
+        
+        /// Show only TOTP secrets
+        #[arg(long)]
+        totps: bool,
+        
+        /// Show only SSH keys
+        #[arg(long)]
+        ssh_keys: bool,
+        
+        /// Show only credit cards
+        #[arg(long)]
+        cards: bool,
+        
+        /// Show only WiFi networks
+        #[arg(long)]
+        wifi: bool,
+        
+        /// Order each section by this field instead of insertion order
+        #[arg(long, value_enum)]
+        sort: Option<SortArg>,
+        
+        /// Reverse the sort order (newest or Z first)
+        #[arg(long, requires = "sort")]
+        desc: bool,
     },
     
     /// Get a specific secret by name or ID
@@ -130,24 +233,157 @@ enum Commands {
         /// Copy the secret value to clipboard
         #[arg(short, long)]
         copy: bool,
+        
+        /// With --copy, clear the clipboard again after this many seconds
+        /// (default: clipboard_clear_seconds in kookie.toml)
+        #[arg(long, value_name = "SECONDS", requires = "copy")]
+        clear_after: Option<u64>,
+        
+        /// Show the secret as a QR code (TOTP secrets as an otpauth:// URI)
+        #[arg(long)]
+        qr: bool,
+        
+        /// Print the secret as JSON with its value masked
+        #[arg(long, conflicts_with_all = ["copy", "qr"])]
+        json: bool,
+        
+        /// With --json, include the secret value
+        #[arg(long, requires = "json")]
+        reveal: bool,
+        
+        /// Print only the secret value, for piping into other commands
+        #[arg(long, conflicts_with_all = ["copy", "qr", "json"])]
+        raw: bool,
+    },
+    
+    /// Show the current code of a TOTP secret and when it expires
+    Otp {
+        /// Name or ID of the TOTP secret
+        name_or_id: String,
+        
+        /// Copy the code to clipboard
+        #[arg(short, long)]
+        copy: bool,
+    },
+    
+    /// Search secrets by name, description and other metadata
+    Search {
+        /// Text to search for (case-insensitive)
+        query: String,
+        
+        /// Only search these secret types (e.g. password,api-key)
+        #[arg(long = "type", value_name = "TYPES", value_delimiter = ',')]
+        types: Vec<SecretType>,
+        
+        /// Show at most this many results
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    
+    /// Print secrets as KEY=value lines for a .env file
+    Env {
+        /// Names of API keys, tokens or database credentials
+        #[arg(required = true)]
+        names: Vec<String>,
+        
+        /// Prefix every variable name, e.g. MYAPP gives MYAPP_STRIPE_KEY
+        #[arg(long)]
+        prefix: Option<String>,
     },
     
     /// Delete a secret
     Delete {
         /// Name or ID of the secret
-        name_or_id: String,
+        #[arg(required_unless_present = "tag", conflicts_with = "tag")]
+        name_or_id: Option<String>,
+        
+        /// Delete every secret with this tag instead
+        #[arg(long)]
+        tag: Option<String>,
         
         /// Skip confirmation prompt
         #[arg(short, long)]
         force: bool,
     },
     
+    /// List, restore or empty deleted secrets
+    Trash {
+        /// Restore a deleted secret by id (or id prefix)
+        #[arg(short, long, value_name = "ID")]
+        restore: Option<String>,
+        
+        /// Permanently delete everything in the trash
+        #[arg(long, conflicts_with = "restore")]
+        empty: bool,
+    },
+    
     /// Generate random secrets
     Generate {
         #[command(subcommand)]
         gen_type: GenerateType,
     },
     
+    /// Export an encrypted backup of the vault
+    Export {
+        /// Destination file
+        path: PathBuf,
+        
+        /// Write unencrypted JSON instead (for migrating to another tool)
+        #[arg(long, conflicts_with = "only")]
+        plaintext: bool,
+        
+        /// Only export these secret types (e.g. token,db-credential)
+        #[arg(long, value_name = "TYPES", value_delimiter = ',')]
+        only: Vec<SecretType>,
+    },
+    
+    /// Import secrets from an encrypted backup
+    Import {
+        /// Backup file to import
+        path: PathBuf,
+        
+        /// What to do when a secret with the same name already exists
+        #[arg(short, long, value_enum, default_value_t = ImportModeArg::Skip)]
+        mode: ImportModeArg,
+        
+        /// The file is a plaintext JSON export
+        #[arg(long)]
+        plaintext: bool,
+        
+        /// Only show what would be imported
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
+    /// Check vault health (integrity, crypto, weak/reused/expired secrets)
+    Doctor,
+    
+    /// Show a summary of what the vault contains
+    Stats,
+    
+    /// Show when secrets were created and last updated
+    History {
+        /// Only show the most recent N events
+        #[arg(short, long)]
+        limit: Option<usize>,
+    },
+    
+    /// Compare the secrets of two vault files (names and flags only, never values)
+    Diff {
+        /// First vault file
+        first: PathBuf,
+        
+        /// Second vault file
+        second: PathBuf,
+    },
+    
+    /// Show the vault file's version and timestamps (no password needed)
+    Info,
+    
+    /// Check stored passwords against Have I Been Pwned
+    #[cfg(feature = "hibp")]
+    Audit,
+    
     /// Configure kookie settings
     Config {
         /// Set unlock timeout in minutes (0 to disable)
@@ -157,6 +393,38 @@ enum Commands {
         /// Show current configuration
         #[arg(short, long)]
         show: bool,
+        
+        /// Refuse unlocking after this many wrong master passwords in a row (0 to disable)
+        #[arg(long, value_name = "ATTEMPTS")]
+        lockout: Option<u32>,
+        
+        /// With --lockout, how long unlocking is refused
+        #[arg(long, value_name = "MINUTES", requires = "lockout", default_value_t = kookie::vault::lockout::DEFAULT_LOCKOUT_MINUTES)]
+        lockout_minutes: u32,
+        
+        /// With --lockout, destroy the vault file instead of waiting (irreversible)
+        #[arg(long, requires = "lockout")]
+        wipe_on_lockout: bool,
+        
+        /// Enforce a password policy with this minimum length (0 with no other rules removes it)
+        #[arg(long, value_name = "CHARS")]
+        min_length: Option<usize>,
+        
+        /// With --min-length, require a symbol
+        #[arg(long, requires = "min_length")]
+        require_symbol: bool,
+        
+        /// With --min-length, require a digit
+        #[arg(long, requires = "min_length")]
+        require_digit: bool,
+        
+        /// With --min-length, require upper- and lowercase letters
+        #[arg(long, requires = "min_length")]
+        require_mixed_case: bool,
+        
+        /// With --min-length, reject common passwords
+        #[arg(long, requires = "min_length")]
+        forbid_common: bool,
     },
     
     /// Install kookie to system PATH
@@ -170,6 +438,78 @@ enum Commands {
     Uninstall,
 }
 
+/// Collision handling for imports
+#[derive(Clone, Copy, ValueEnum)]
+enum ImportModeArg {
+    /// Keep existing secrets
+    Skip,
+    /// Overwrite existing secrets
+    Replace,
+    /// Keep both, renaming the imported secret
+    Rename,
+    /// Keep whichever was updated most recently
+    NewestWins,
+}
+
+impl From<ImportModeArg> for ImportMode {
+    fn from(mode: ImportModeArg) -> Self {
+        match mode {
+            ImportModeArg::Skip => ImportMode::Skip,
+            ImportModeArg::Replace => ImportMode::Replace,
+            ImportModeArg::Rename => ImportMode::Rename,
+            ImportModeArg::NewestWins => ImportMode::NewestWins,
+        }
+    }
+}
+
+/// Sort field for `list --sort`
+#[derive(Clone, Copy, ValueEnum)]
+enum SortArg {
+    /// Alphabetical by name
+    Name,
+    /// When the secret was added
+    Created,
+    /// When the secret was last changed
+    Updated,
+}
+
+impl From<SortArg> for SortKey {
+    fn from(sort: SortArg) -> Self {
+        match sort {
+            SortArg::Name => SortKey::Name,
+            SortArg::Created => SortKey::CreatedAt,
+            SortArg::Updated => SortKey::UpdatedAt,
+        }
+    }
+}
+
+/// Presets for `add --template`
+#[derive(Clone, Copy, ValueEnum)]
+enum TemplateArg {
+    /// Stripe API key
+    Stripe,
+    /// OpenAI API key
+    Openai,
+    /// GitHub personal access token
+    GithubPat,
+    /// Postgres database on localhost:5432
+    PostgresLocal,
+    /// MySQL database on localhost:3306
+    MysqlLocal,
+}
+
+impl From<TemplateArg> for commands::add::Template {
+    fn from(template: TemplateArg) -> Self {
+        match template {
+            TemplateArg::Stripe => Self::Stripe,
+            TemplateArg::Openai => Self::OpenAi,
+            TemplateArg::GithubPat => Self::GithubPat,
+            TemplateArg::PostgresLocal => Self::PostgresLocal,
+            TemplateArg::MysqlLocal => Self::MysqlLocal,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum GenerateType {
     /// Generate a JWT secret (256-bit)
@@ -192,14 +532,30 @@ enum GenerateType {
     
     /// Generate a random password
     Password {
-        /// Length in characters (default: 16)
+        /// Length in characters (default: 16, or password_length in kookie.toml)
         #[arg(short, long)]
         length: Option<usize>,
         
-        /// Include symbols
-        #[arg(short, long)]
+        /// Include symbols (default: include_symbols in kookie.toml)
+        #[arg(short, long, overrides_with = "no_symbols")]
         symbols: bool,
         
+        /// Leave out symbols, even if include_symbols is set in kookie.toml
+        #[arg(long, overrides_with = "symbols")]
+        no_symbols: bool,
+        
+        /// Leave out look-alike characters (l, 1, I, 0, O, ...)
+        #[arg(long)]
+        no_ambiguous: bool,
+        
+        /// Include at least this many digits
+        #[arg(long, default_value_t = 0)]
+        min_digits: usize,
+        
+        /// Include at least this many symbols
+        #[arg(long, default_value_t = 0)]
+        min_symbols: usize,
+        
         /// Copy to clipboard
         #[arg(short, long)]
         copy: bool,
@@ -212,20 +568,80 @@ enum GenerateType {
         #[arg(short, long)]
         copy: bool,
     },
+    
+    /// Generate a diceware passphrase from the EFF wordlist
+    Passphrase {
+        /// Number of words (default: 6)
+        #[arg(short, long)]
+        words: Option<usize>,
+        
+        /// Separator between words
+        #[arg(long, default_value = "-")]
+        separator: String,
+        
+        /// Capitalize each word
+        #[arg(long)]
+        capitalize: bool,
+        
+        /// Copy to clipboard
+        #[arg(short, long)]
+        copy: bool,
+    },
+    
+    /// Generate a pronounceable password of consonant-vowel syllables
+    Pronounceable {
+        /// Number of syllables (default: 5)
+        #[arg(short, long)]
+        syllables: Option<usize>,
+        
+        /// Capitalize the first letter
+        #[arg(long)]
+        capitalize: bool,
+        
+        /// Append this many digits
+        #[arg(long, default_value_t = 0)]
+        digits: usize,
+        
+        /// Copy to clipboard
+        #[arg(short, long)]
+        copy: bool,
+    },
+    
+    /// Generate a value matching a pattern (L letter, d digit, s symbol, A alphanumeric)
+    Pattern {
+        /// Pattern such as LLdd-ssss; escape literal letters with a backslash
+        pattern: String,
+        
+        /// Copy to clipboard
+        #[arg(short, long)]
+        copy: bool,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
     
+    if let Err(e) = Config::load() {
+        eprintln!("{} {} (using defaults)", "Warning:".yellow().bold(), e);
+    }
+    
     let result = match cli.command {
-        Commands::Init { force } => commands::init::run(force),
+        Commands::Init { force, cipher } => commands::init::run(force, cipher),
         
         Commands::Lock => commands::lock::lock(),
         
         Commands::Unlock { timeout } => commands::lock::unlock(timeout),
         
-        Commands::Add { password, api_key, note, db, token } => {
-            let add_type = if password {
+        Commands::ChangePassword => commands::lock::change_password(),
+        
+        Commands::RecoveryKey => commands::recovery::generate(),
+        
+        Commands::Recover => commands::recovery::recover(),
+        
+        Commands::Add { password, api_key, note, db, token, totp, ssh_key, card, wifi, template, generate } => {
+            let add_type = if let Some(template) = template {
+                commands::add::AddType::Template(template.into())
+            } else if password {
                 commands::add::AddType::Password
             } else if api_key {
                 commands::add::AddType::ApiKey
@@ -235,6 +651,14 @@ fn main() {
                 commands::add::AddType::DbCredential
             } else if token {
                 commands::add::AddType::Token
+            } else if totp {
+                commands::add::AddType::Totp
+            } else if ssh_key {
+                commands::add::AddType::SshKey
+            } else if card {
+                commands::add::AddType::CreditCard
+            } else if wifi {
+                commands::add::AddType::Wifi
             } else {
                 println!("{}", "Please specify a secret type:".yellow());
                 println!("  kookie add --password");
@@ -242,12 +666,17 @@ fn main() {
                 println!("  kookie add --note");
                 println!("  kookie add --db");
                 println!("  kookie add --token");
+                println!("  kookie add --totp");
+                println!("  kookie add --ssh-key");
+                println!("  kookie add --card");
+                println!("  kookie add --wifi");
+                println!("  kookie add --template <NAME>");
                 return;
             };
-            commands::add::run(add_type)
+            commands::add::run(add_type, generate)
         }
         
-        Commands::List { passwords, api_keys, notes, db, tokens } => {
+        Commands::List { passwords, api_keys, notes, db, tokens, totps, ssh_keys, cards, wifi, sort, desc } => {
             let filter = if passwords {
                 commands::list::ListFilter::Passwords
             } else if api_keys {
@@ -258,34 +687,127 @@ fn main() {
                 commands::list::ListFilter::DbCredentials
             } else if tokens {
                 commands::list::ListFilter::Tokens
+            } else if totps {
+                commands::list::ListFilter::Totps
+            } else if ssh_keys {
+                commands::list::ListFilter::SshKeys
+            } else if cards {
+                commands::list::ListFilter::CreditCards
+            } else if wifi {
+                commands::list::ListFilter::Wifi
             } else {
                 commands::list::ListFilter::All
             };
-            commands::list::run(filter)
+            commands::list::run(filter, sort.map(Into::into), desc)
         }
         
-        Commands::Get { name_or_id, copy } => commands::get::run(&name_or_id, copy),
+        Commands::Get { name_or_id, json: true, reveal, .. } => commands::get::run_json(&name_or_id, reveal),
         
-        Commands::Delete { name_or_id, force } => commands::delete::run(&name_or_id, force),
+        Commands::Get { name_or_id, raw: true, .. } => commands::get::run_raw(&name_or_id),
+        
+        Commands::Get { name_or_id, copy, clear_after, qr, .. } => {
+            let clear_after = clear_after
+                .map(std::time::Duration::from_secs)
+                .or_else(|| Config::current().clipboard_clear_after());
+            commands::get::run(&name_or_id, copy, qr, clear_after)
+        }
+        
+        Commands::Otp { name_or_id, copy } => commands::otp::run(&name_or_id, copy),
+        
+        Commands::Search { query, types, limit } => commands::search::run(&query, &types, limit),
+        
+        Commands::Env { names, prefix } => commands::env::run(&names, prefix.as_deref()),
+        
+        Commands::Delete { name_or_id, tag, force } => match (name_or_id, tag) {
+            (_, Some(tag)) => commands::delete::run_by_tag(&tag, force),
+            (Some(name_or_id), None) => commands::delete::run(&name_or_id, force),
+            (None, None) => unreachable!("clap requires a name or --tag"),
+        },
+        
+        Commands::Trash { restore, empty } => commands::trash::run(restore.as_deref(), empty),
         
         Commands::Generate { gen_type } => {
             match gen_type {
                 GenerateType::Jwt { copy } => {
-                    commands::generate::run(commands::generate::GenerateType::Jwt, None, copy, false)
+                    commands::generate::run(commands::generate::GenerateType::Jwt, None, copy, None)
                 }
                 GenerateType::Key { length, copy } => {
-                    commands::generate::run(commands::generate::GenerateType::Key, length, copy, false)
+                    commands::generate::run(commands::generate::GenerateType::Key, length, copy, None)
                 }
-                GenerateType::Password { length, symbols, copy } => {
-                    commands::generate::run(commands::generate::GenerateType::Password, length, copy, symbols)
+                GenerateType::Password { length, symbols, no_symbols, no_ambiguous, min_digits, min_symbols, copy } => {
+                    commands::generate::run(
+                        commands::generate::GenerateType::Password {
+                            exclude_ambiguous: no_ambiguous,
+                            min_digits,
+                            min_symbols,
+                        },
+                        length,
+                        copy,
+                        (symbols || no_symbols).then_some(symbols),
+                    )
                 }
                 GenerateType::ApiKey { copy } => {
-                    commands::generate::run(commands::generate::GenerateType::ApiKey, None, copy, false)
+                    commands::generate::run(commands::generate::GenerateType::ApiKey, None, copy, None)
+                }
+                GenerateType::Passphrase { words, separator, capitalize, copy } => {
+                    commands::generate::run(
+                        commands::generate::GenerateType::Passphrase { separator, capitalize },
+                        words,
+                        copy,
+                        None,
+                    )
+                }
+                GenerateType::Pronounceable { syllables, capitalize, digits, copy } => {
+                    commands::generate::run(
+                        commands::generate::GenerateType::Pronounceable { capitalize, digits },
+                        syllables,
+                        copy,
+                        None,
+                    )
+                }
+                GenerateType::Pattern { pattern, copy } => {
+                    commands::generate::run(commands::generate::GenerateType::Pattern(pattern), None, copy, None)
                 }
             }
         }
         
-        Commands::Config { timeout, show } => commands::config::run(timeout, show),
+        Commands::Export { path, plaintext, only } => commands::backup::export(&path, plaintext, &only),
+        
+        Commands::Import { path, mode, plaintext, dry_run } => {
+            commands::backup::import(&path, mode.into(), plaintext, dry_run)
+        }
+        
+        Commands::Doctor => commands::doctor::run(),
+        
+        Commands::Stats => commands::stats::run(),
+        
+        Commands::History { limit } => commands::history::run(limit),
+        
+        Commands::Diff { first, second } => commands::diff::run(&first, &second),
+        
+        Commands::Info => commands::info::run(),
+        
+        #[cfg(feature = "hibp")]
+        Commands::Audit => commands::audit::run(),
+        
+        Commands::Config {
+            timeout, show, lockout, lockout_minutes, wipe_on_lockout,
+            min_length, require_symbol, require_digit, require_mixed_case, forbid_common,
+        } => {
+            let lockout = lockout.map(|attempts| commands::config::LockoutArgs {
+                attempts,
+                minutes: lockout_minutes,
+                wipe: wipe_on_lockout,
+            });
+            let policy = min_length.map(|min_length| PasswordPolicy {
+                min_length,
+                require_symbol,
+                require_digit,
+                require_mixed_case,
+                forbid_common,
+            });
+            commands::config::run(timeout, show, lockout, policy)
+        }
         
         Commands::Install { force } => commands::install::run(force),
         
@@ -8,6 +8,7 @@ use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Session configuration
 #[derive(Serialize, Deserialize, Clone)]
@@ -31,6 +32,8 @@ struct SessionData {
     key_data: String,
     /// Expiration time
     expires_at: DateTime<Utc>,
+    /// Canonical path of the vault the key belongs to
+    vault_path: PathBuf,
 }
 
 /// Gets a simple machine-specific key for session encryption
@@ -64,8 +67,17 @@ fn get_machine_key() -> [u8; 32] {
     key
 }
 
-/// Saves a session with the encryption key
-pub fn save_session(key: &[u8; 32], timeout_minutes: u32) -> Result<(), std::io::Error> {
+/// Saves a session with the encryption key of the vault at `vault_path`
+pub fn save_session(key: &[u8; 32], vault_path: &Path, timeout_minutes: u32) -> Result<(), std::io::Error> {
+    save_session_at(&storage::get_session_path(), key, vault_path, timeout_minutes)
+}
+
+fn save_session_at(
+    session_path: &Path,
+    key: &[u8; 32],
+    vault_path: &Path,
+    timeout_minutes: u32,
+) -> Result<(), std::io::Error> {
     if timeout_minutes == 0 {
         return Ok(()); // Don't save session if timeout is 0
     }
@@ -81,29 +93,38 @@ pub fn save_session(key: &[u8; 32], timeout_minutes: u32) -> Result<(), std::io:
     let session = SessionData {
         key_data: BASE64.encode(obfuscated),
         expires_at: Utc::now() + Duration::minutes(timeout_minutes as i64),
+        vault_path: canonical(vault_path),
     };
 
-    let path = storage::get_session_path();
     let content = serde_json::to_string(&session)?;
-    fs::write(path, content)?;
+    fs::write(session_path, content)?;
     
     Ok(())
 }
 
-/// Gets the cached key if session is still valid
-pub fn get_cached_key() -> Option<[u8; 32]> {
-    let path = storage::get_session_path();
-    
-    if !path.exists() {
+/// Gets the cached key if a session for the vault at `vault_path` is still valid
+///
+/// A session saved for a different vault (another profile, or another
+/// `KOOKIE_VAULT_PATH`) counts as no session.
+pub fn get_cached_key(vault_path: &Path) -> Option<[u8; 32]> {
+    cached_key_at(&storage::get_session_path(), vault_path)
+}
+
+fn cached_key_at(session_path: &Path, vault_path: &Path) -> Option<[u8; 32]> {
+    if !session_path.exists() {
         return None;
     }
     
-    let content = fs::read_to_string(&path).ok()?;
+    let content = fs::read_to_string(session_path).ok()?;
     let session: SessionData = serde_json::from_str(&content).ok()?;
     
     // Check if expired
     if session.expires_at < Utc::now() {
-        let _ = clear_session();
+        let _ = fs::remove_file(session_path);
+        return None;
+    }
+    
+    if session.vault_path != canonical(vault_path) {
         return None;
     }
     
@@ -122,6 +143,11 @@ pub fn get_cached_key() -> Option<[u8; 32]> {
     Some(key)
 }
 
+/// Resolves `path` so different spellings of the same vault compare equal
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
 /// Clears the session
 pub fn clear_session() -> Result<(), std::io::Error> {
     let path = storage::get_session_path();
@@ -146,9 +172,37 @@ pub fn load_config() -> SessionConfig {
 
 /// Saves session configuration
 pub fn save_config(config: &SessionConfig) -> Result<(), std::io::Error> {
-    storage::ensure_vault_dir().map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+    storage::ensure_vault_dir().map_err(|e| std::io::Error::other(e.to_string()))?;
     let path = storage::get_config_path();
     let content = serde_json::to_string_pretty(config)?;
     fs::write(path, content)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_belongs_to_one_vault() {
+        let dir = tempfile::tempdir().unwrap();
+        let session_path = dir.path().join(".session");
+        let work = dir.path().join("work.json");
+        let personal = dir.path().join("personal.json");
+        fs::write(&work, "{}").unwrap();
+        fs::write(&personal, "{}").unwrap();
+
+        save_session_at(&session_path, &[1; 32], &work, 10).unwrap();
+        assert_eq!(cached_key_at(&session_path, &work), Some([1; 32]));
+        assert_eq!(
+            cached_key_at(&session_path, &dir.path().join(".").join("work.json")),
+            Some([1; 32])
+        );
+        assert_eq!(cached_key_at(&session_path, &personal), None);
+
+        // Unlocking the other vault takes over the session
+        save_session_at(&session_path, &[2; 32], &personal, 10).unwrap();
+        assert_eq!(cached_key_at(&session_path, &personal), Some([2; 32]));
+        assert_eq!(cached_key_at(&session_path, &work), None);
+    }
+}
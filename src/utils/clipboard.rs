@@ -1,10 +1,85 @@
 //! Clipboard utilities
 
 use arboard::Clipboard;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use zeroize::Zeroizing;
 
-/// Copies text to clipboard
-pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+/// Handle to a pending clipboard clear
+///
+/// Dropping the guard detaches the clearing thread; call `wait` to keep the
+/// process alive until the clipboard has been cleared.
+pub struct ClearGuard {
+    handle: JoinHandle<()>,
+}
+
+impl ClearGuard {
+    /// Blocks until the clipboard has been cleared
+    pub fn wait(self) {
+        let _ = self.handle.join();
+    }
+}
+
+/// Copies text to clipboard, optionally clearing it again after `clear_after`
+///
+/// The clipboard is only cleared if it still holds `value`, so anything the
+/// user copied in the meantime is left alone.
+pub fn copy_to_clipboard(
+    value: &str,
+    clear_after: Option<Duration>,
+) -> Result<Option<ClearGuard>, String> {
     let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
-    clipboard.set_text(text).map_err(|e| e.to_string())?;
-    Ok(())
+    clipboard.set_text(value).map_err(|e| e.to_string())?;
+
+    let Some(delay) = clear_after else {
+        return Ok(None);
+    };
+
+    let value = Zeroizing::new(value.to_string());
+    let handle = thread::spawn(move || {
+        thread::sleep(delay);
+        let still_ours = clipboard
+            .get_text()
+            .map(Zeroizing::new)
+            .is_ok_and(|current| *current == *value);
+        if still_ours {
+            let _ = clipboard.set_text(String::new());
+        }
+    });
+
+    Ok(Some(ClearGuard { handle }))
+}
+
+/// Reads the current clipboard text
+pub fn read_clipboard() -> Result<String, String> {
+    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+    clipboard.get_text().map_err(|e| e.to_string())
+}
+
+// Needs a desktop session, so only runs with `--features clipboard-tests`
+#[cfg(all(test, feature = "clipboard-tests"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_and_auto_clear() {
+        copy_to_clipboard("kookie-clipboard-test", None).unwrap();
+        assert_eq!(read_clipboard().unwrap(), "kookie-clipboard-test");
+
+        let guard = copy_to_clipboard("kookie-clear-test", Some(Duration::from_millis(200)))
+            .unwrap()
+            .unwrap();
+        guard.wait();
+        assert_eq!(read_clipboard().unwrap(), "");
+    }
+
+    #[test]
+    fn test_auto_clear_keeps_newer_content() {
+        let guard = copy_to_clipboard("kookie-old", Some(Duration::from_millis(300)))
+            .unwrap()
+            .unwrap();
+        copy_to_clipboard("kookie-newer", None).unwrap();
+        guard.wait();
+        assert_eq!(read_clipboard().unwrap(), "kookie-newer");
+    }
 }
@@ -1,6 +1,7 @@
 //! Display utilities for formatting output
 
 use crate::vault::types::*;
+use chrono::Utc;
 use colored::*;
 
 /// Prints a success message
@@ -23,6 +24,17 @@ pub fn info(msg: &str) {
     println!("{} {}", "ℹ".blue().bold(), msg);
 }
 
+/// Prints "did you mean" candidates for a name that wasn't found
+pub fn suggestions(candidates: &[(SecretType, String)]) {
+    if candidates.is_empty() {
+        return;
+    }
+    println!("{}", "Did you mean:".dimmed());
+    for (secret_type, name) in candidates {
+        println!("  {} {}", name.white().bold(), format!("({})", secret_type).dimmed());
+    }
+}
+
 /// Prints a secret value (masked by default)
 pub fn print_secret(label: &str, value: &str, show: bool) {
     let display = if show {
@@ -47,12 +59,14 @@ pub fn display_password(password: &Password, show_secret: bool) {
         println!("{} {}", "Username:".dimmed(), username.green());
     }
     
-    print_secret("Password", &password.password, show_secret);
+    print_secret("Password", password.password.expose_secret(), show_secret);
     
     if let Some(url) = &password.url {
         println!("{} {}", "URL:".dimmed(), url.blue().underline());
     }
     
+    display_custom_fields(&password.custom_fields, show_secret);
+    
     println!("{} {}", "Created:".dimmed(), password.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
 }
@@ -71,7 +85,9 @@ pub fn display_api_key(api_key: &ApiKey, show_secret: bool) {
         println!("{} {}", "Service:".dimmed(), service.green());
     }
     
-    print_secret("Key", &api_key.key, show_secret);
+    print_secret("Key", api_key.key.expose_secret(), show_secret);
+    
+    display_custom_fields(&api_key.custom_fields, show_secret);
     
     println!("{} {}", "Created:".dimmed(), api_key.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
@@ -84,17 +100,45 @@ pub fn display_note(note: &Note, show_content: bool) {
     println!("{} {}", "ID:".dimmed(), note.id.cyan());
     println!("{} {}", "Name:".dimmed(), note.name.white().bold());
     
+    if let Some(desc) = &note.description {
+        println!("{} {}", "Description:".dimmed(), desc);
+    }
+    
     if show_content {
         println!("{}", "Content:".dimmed());
-        println!("{}", note.content.yellow());
+        println!("{}", note.content.expose_secret().yellow());
     } else {
         println!("{} {}", "Content:".dimmed(), "••••••••".yellow());
     }
     
+    for attachment in &note.attachments {
+        println!(
+            "{} {} ({}, {} bytes)",
+            "Attachment:".dimmed(),
+            attachment.filename,
+            attachment.mime_type,
+            attachment.size
+        );
+    }
+    
     println!("{} {}", "Created:".dimmed(), note.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
 }
 
+/// Prints custom fields, masking hidden values unless `show_secret` is set
+fn display_custom_fields(fields: &[CustomField], show_secret: bool) {
+    for field in fields {
+        let label = format!("{}:", field.name);
+        if field.hidden && !show_secret {
+            println!("{} {}", label.dimmed(), "••••••••".yellow());
+        } else if field.hidden {
+            println!("{} {}", label.dimmed(), field.value.expose_secret().yellow());
+        } else {
+            println!("{} {}", label.dimmed(), field.value.expose_secret());
+        }
+    }
+}
+
 /// Formats a database credential for display
 pub fn display_db_credential(cred: &DbCredential, show_secret: bool) {
     println!();
@@ -116,11 +160,12 @@ pub fn display_db_credential(cred: &DbCredential, show_secret: bool) {
     println!("{} {}", "Database:".dimmed(), cred.database);
     println!("{} {}", "Username:".dimmed(), cred.username.green());
     
-    print_secret("Password", &cred.password, show_secret);
+    print_secret("Password", cred.password.expose_secret(), show_secret);
     
     if show_secret {
         println!("{} {}", "Connection String:".dimmed(), cred.connection_string().blue());
     }
+    println!("{} {}", "Connection Template:".dimmed(), cred.connection_string_with_env().blue());
     
     println!("{} {}", "Created:".dimmed(), cred.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
@@ -140,7 +185,7 @@ pub fn display_token(token: &Token, show_secret: bool) {
         println!("{} {}", "Type:".dimmed(), token_type.green());
     }
     
-    print_secret("Token", &token.token, show_secret);
+    print_secret("Token", token.token.expose_secret(), show_secret);
     
     if let Some(expires) = token.expires_at {
         let status = if token.is_expired() {
@@ -151,10 +196,113 @@ pub fn display_token(token: &Token, show_secret: bool) {
         println!("{} {} ({})", "Expires:".dimmed(), expires.format("%Y-%m-%d %H:%M"), status);
     }
     
+    display_custom_fields(&token.custom_fields, show_secret);
+    
     println!("{} {}", "Created:".dimmed(), token.created_at.format("%Y-%m-%d %H:%M"));
     println!("{}", "═".repeat(50).dimmed());
 }
 
+/// Formats a TOTP secret for display
+pub fn display_totp(totp: &Totp, show_secret: bool) {
+    println!();
+    println!("{}", "═".repeat(50).dimmed());
+    println!("{} {}", "ID:".dimmed(), totp.id.cyan());
+    println!("{} {}", "Name:".dimmed(), totp.name.white().bold());
+    
+    if let Some(issuer) = &totp.issuer {
+        println!("{} {}", "Issuer:".dimmed(), issuer.green());
+    }
+    
+    print_secret("Secret", totp.secret.expose_secret(), show_secret);
+    
+    match totp.code_with_ttl(Utc::now()) {
+        Ok((code, remaining)) => println!(
+            "{} {} {}",
+            "Code:".dimmed(),
+            code.yellow().bold(),
+            format!("(expires in {}s)", remaining).dimmed()
+        ),
+        Err(e) => println!("{} {}", "Code:".dimmed(), e.to_string().red()),
+    }
+    
+    println!("{} {}", "Created:".dimmed(), totp.created_at.format("%Y-%m-%d %H:%M"));
+    println!("{}", "═".repeat(50).dimmed());
+}
+
+/// Formats an SSH key for display
+pub fn display_ssh_key(key: &SshKey, show_secret: bool) {
+    println!();
+    println!("{}", "═".repeat(50).dimmed());
+    println!("{} {}", "ID:".dimmed(), key.id.cyan());
+    println!("{} {}", "Name:".dimmed(), key.name.white().bold());
+    
+    if let Some(desc) = &key.description {
+        println!("{} {}", "Description:".dimmed(), desc);
+    }
+    
+    println!("{} {}", "Type:".dimmed(), key.key_type.to_string().green());
+    println!("{} {}", "Public key:".dimmed(), key.public_key);
+    
+    if show_secret {
+        println!("{}", "Private key:".dimmed());
+        println!("{}", key.private_key.expose_secret().yellow());
+    } else {
+        print_secret("Private key", key.private_key.expose_secret(), false);
+    }
+    
+    if let Some(passphrase) = &key.passphrase {
+        print_secret("Passphrase", passphrase.expose_secret(), show_secret);
+    }
+    
+    println!("{} {}", "Created:".dimmed(), key.created_at.format("%Y-%m-%d %H:%M"));
+    println!("{}", "═".repeat(50).dimmed());
+}
+
+/// Formats a credit card for display
+pub fn display_credit_card(card: &CreditCard, show_secret: bool) {
+    println!();
+    println!("{}", "═".repeat(50).dimmed());
+    println!("{} {}", "ID:".dimmed(), card.id.cyan());
+    println!("{} {}", "Name:".dimmed(), card.name.white().bold());
+    println!("{} {}", "Cardholder:".dimmed(), card.cardholder);
+    
+    if let Some(brand) = &card.brand {
+        println!("{} {}", "Brand:".dimmed(), brand.green());
+    }
+    
+    if show_secret {
+        print_secret("Number", card.number.expose_secret(), true);
+    } else {
+        print_secret("Number", &format!("•••• {}", card.last_four()), true);
+    }
+    println!("{} {:02}/{}", "Expires:".dimmed(), card.expiry_month, card.expiry_year);
+    print_secret("CVV", card.cvv.expose_secret(), show_secret);
+    
+    println!("{} {}", "Created:".dimmed(), card.created_at.format("%Y-%m-%d %H:%M"));
+    println!("{}", "═".repeat(50).dimmed());
+}
+
+/// Formats a WiFi credential for display
+pub fn display_wifi_credential(wifi: &WifiCredential, show_secret: bool) {
+    println!();
+    println!("{}", "═".repeat(50).dimmed());
+    println!("{} {}", "ID:".dimmed(), wifi.id.cyan());
+    println!("{} {}", "Name:".dimmed(), wifi.name.white().bold());
+    println!("{} {}", "SSID:".dimmed(), wifi.ssid.green());
+    println!("{} {}", "Security:".dimmed(), wifi.security);
+    
+    if wifi.hidden {
+        println!("{} yes", "Hidden:".dimmed());
+    }
+    
+    if wifi.security != WifiSecurity::Open {
+        print_secret("Password", wifi.password.expose_secret(), show_secret);
+    }
+    
+    println!("{} {}", "Created:".dimmed(), wifi.created_at.format("%Y-%m-%d %H:%M"));
+    println!("{}", "═".repeat(50).dimmed());
+}
+
 /// Prints a list header
 pub fn list_header(secret_type: &str, count: usize) {
     println!();
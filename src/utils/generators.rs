@@ -1,15 +1,86 @@
 //! Key and secret generators
+//!
+//! Every generator has a `_with_rng` variant taking any `RngCore`, so tests
+//! (or deterministic derivation from a seed) can supply their own source of
+//! randomness. The plain functions use `thread_rng()`.
 
+use crate::config::Config;
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use rand::RngCore;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Generator errors
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum GenError {
+    #[error("Unknown pattern token '{0}' (use L, d, s, A or escape literals with \\)")]
+    UnknownToken(char),
+    #[error("Pattern ends with an unfinished escape")]
+    DanglingEscape,
+    #[error("Minimum digits and symbols ({required}) exceed the password length ({length})")]
+    MinimumsExceedLength { required: usize, length: usize },
+}
+
+/// EFF large wordlist (https://www.eff.org/dice, CC BY 3.0): 7776 "<dice>\t<word>" lines
+pub static EFF_LARGE_WORDLIST: &str = include_str!("eff_large_wordlist.txt");
 
 /// Generates a random key of specified length
 pub fn generate_random_key(length: usize) -> String {
+    generate_random_key_with_rng(&mut rand::thread_rng(), length)
+}
+
+/// Generates a random key of `length` bytes from `rng`
+pub fn generate_random_key_with_rng(rng: &mut impl RngCore, length: usize) -> String {
     let mut bytes = vec![0u8; length];
-    rand::thread_rng().fill_bytes(&mut bytes);
+    rng.fill_bytes(&mut bytes);
     URL_SAFE_NO_PAD.encode(&bytes)
 }
 
+/// Generates a random key and returns it with its entropy in bits
+pub fn generate_random_key_with_entropy(bytes: usize) -> (String, f64) {
+    (generate_random_key(bytes), (bytes * 8) as f64)
+}
+
+/// Estimates the entropy of an encoded key from its length and alphabet
+///
+/// The alphabet is the smallest common one that covers every character
+/// (hex, alphanumeric classes, base64, or all printable ASCII). This is an
+/// upper bound: it assumes each character was chosen uniformly at random.
+pub fn encoded_key_entropy(key: &str) -> f64 {
+    let key = key.trim_end_matches('=');
+    let is_hex = key.chars().all(|c| c.is_ascii_hexdigit())
+        && !(key.chars().any(|c| c.is_ascii_lowercase())
+            && key.chars().any(|c| c.is_ascii_uppercase()));
+
+    let pool = if key.is_empty() {
+        1
+    } else if is_hex && key.chars().any(|c| c.is_ascii_alphabetic()) {
+        16
+    } else if key.chars().all(|c| c.is_ascii_alphanumeric()) {
+        [
+            (key.chars().any(|c| c.is_ascii_lowercase()), 26),
+            (key.chars().any(|c| c.is_ascii_uppercase()), 26),
+            (key.chars().any(|c| c.is_ascii_digit()), 10),
+        ]
+        .iter()
+        .filter(|(present, _)| *present)
+        .map(|(_, size)| size)
+        .sum()
+    } else if key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_".contains(c))
+        || key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "+/".contains(c))
+    {
+        64
+    } else {
+        95
+    };
+
+    key.chars().count() as f64 * f64::from(pool).log2()
+}
+
 /// Generates a random key suitable for JWT secrets (256 bits)
 pub fn generate_jwt_secret() -> String {
     generate_random_key(32) // 256 bits
@@ -23,37 +94,339 @@ pub fn generate_encryption_key() -> String {
 
 /// Generates a random API key
 pub fn generate_api_key() -> String {
+    generate_api_key_with_rng(&mut rand::thread_rng())
+}
+
+/// Generates a random API key from `rng`
+pub fn generate_api_key_with_rng(rng: &mut impl RngCore) -> String {
     let mut bytes = [0u8; 24];
-    rand::thread_rng().fill_bytes(&mut bytes);
+    rng.fill_bytes(&mut bytes);
     format!("kk_{}", URL_SAFE_NO_PAD.encode(bytes))
 }
 
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"0123456789";
+const SYMBOLS: &[u8] = b"!@#$%^&*()_+-=[]{}|;:,.<>?";
+
+/// Characters that are easily confused with each other when read or typed
+const AMBIGUOUS: &[u8] = b"lI1|oO0";
+
+/// Options for `generate_password_with`
+///
+/// Lowercase letters are always included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordOptions {
+    pub length: usize,
+    pub include_symbols: bool,
+    pub include_digits: bool,
+    pub include_uppercase: bool,
+    /// Leave out look-alike characters such as `l`, `1`, `I`, `0` and `O`
+    pub exclude_ambiguous: bool,
+    /// Guarantee at least one character from every enabled class
+    pub require_each_class: bool,
+    /// Place at least this many digits, even if `include_digits` is off
+    pub min_digits: usize,
+    /// Place at least this many symbols, even if `include_symbols` is off
+    pub min_symbols: usize,
+}
+
+impl Default for PasswordOptions {
+    fn default() -> Self {
+        Self {
+            length: 16,
+            include_symbols: false,
+            include_digits: true,
+            include_uppercase: true,
+            exclude_ambiguous: false,
+            require_each_class: false,
+            min_digits: 0,
+            min_symbols: 0,
+        }
+    }
+}
+
+impl PasswordOptions {
+    /// Character classes enabled by these options
+    fn classes(&self) -> Vec<Vec<u8>> {
+        let mut classes = vec![LOWERCASE];
+        if self.include_uppercase {
+            classes.push(UPPERCASE);
+        }
+        if self.include_digits {
+            classes.push(DIGITS);
+        }
+        if self.include_symbols {
+            classes.push(SYMBOLS);
+        }
+
+        classes.into_iter().map(|class| self.pool(class)).collect()
+    }
+
+    /// `class` without the characters these options leave out
+    fn pool(&self, class: &[u8]) -> Vec<u8> {
+        class
+            .iter()
+            .copied()
+            .filter(|c| !self.exclude_ambiguous || !AMBIGUOUS.contains(c))
+            .collect()
+    }
+}
+
 /// Generates a secure random password
 pub fn generate_password(length: usize, include_symbols: bool) -> String {
-    const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
-    const DIGITS: &[u8] = b"0123456789";
-    const SYMBOLS: &[u8] = b"!@#$%^&*()_+-=[]{}|;:,.<>?";
-    
-    let charset: Vec<u8> = if include_symbols {
-        [LETTERS, DIGITS, SYMBOLS].concat()
-    } else {
-        [LETTERS, DIGITS].concat()
+    let opts = PasswordOptions {
+        length,
+        include_symbols,
+        ..Default::default()
     };
-    
-    let mut password = Vec::with_capacity(length);
-    let mut rng = rand::thread_rng();
-    
-    for _ in 0..length {
-        let idx = (rng.next_u32() as usize) % charset.len();
-        password.push(charset[idx]);
+    generate_password_with(&opts).expect("no minimums to exceed the length")
+}
+
+/// Generates a password with the length and symbols set in `kookie.toml`
+pub fn generate_configured_password() -> String {
+    generate_password_with(&Config::current().password_options()).expect("config sets no minimums")
+}
+
+/// Generates a secure random password according to `opts`
+pub fn generate_password_with(opts: &PasswordOptions) -> Result<String, GenError> {
+    generate_password_with_rng(&mut rand::thread_rng(), opts)
+}
+
+/// Generates a password according to `opts` from `rng`
+///
+/// The minimum digits and symbols are placed first, the rest is filled from
+/// every enabled class and the result shuffled.
+pub fn generate_password_with_rng(
+    rng: &mut impl RngCore,
+    opts: &PasswordOptions,
+) -> Result<String, GenError> {
+    let required = opts.min_digits + opts.min_symbols;
+    if required > opts.length {
+        return Err(GenError::MinimumsExceedLength {
+            required,
+            length: opts.length,
+        });
+    }
+
+    let classes = opts.classes();
+    let charset: Vec<u8> = classes.concat();
+
+    let mut password = Vec::with_capacity(opts.length);
+
+    for (class, count) in [(DIGITS, opts.min_digits), (SYMBOLS, opts.min_symbols)] {
+        let pool = opts.pool(class);
+        for _ in 0..count {
+            password.push(pool[random_index(rng, pool.len())]);
+        }
+    }
+    if opts.require_each_class {
+        for class in classes.iter().take(opts.length - password.len()) {
+            password.push(class[random_index(rng, class.len())]);
+        }
+    }
+    while password.len() < opts.length {
+        password.push(charset[random_index(rng, charset.len())]);
+    }
+
+    if opts.require_each_class || required > 0 {
+        // Fisher-Yates, so the guaranteed characters aren't always up front
+        for i in (1..password.len()).rev() {
+            password.swap(i, random_index(rng, i + 1));
+        }
+    }
+
+    Ok(String::from_utf8(password)
+        .unwrap_or_else(|_| generate_random_key_with_rng(rng, opts.length)))
+}
+
+/// Generates a diceware-style passphrase from the EFF large wordlist
+///
+/// Each word adds about 12.9 bits of entropy.
+pub fn generate_passphrase(word_count: usize, separator: &str, capitalize: bool) -> String {
+    generate_passphrase_with_rng(&mut rand::thread_rng(), word_count, separator, capitalize)
+}
+
+/// Generates a passphrase (see `generate_passphrase`) from `rng`
+pub fn generate_passphrase_with_rng(
+    rng: &mut impl RngCore,
+    word_count: usize,
+    separator: &str,
+    capitalize: bool,
+) -> String {
+    let words = wordlist();
+
+    (0..word_count)
+        .map(|_| {
+            let word = words[random_index(rng, words.len())];
+            if capitalize {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+/// Generates a string following `pattern`, one character per token
+///
+/// - `L`: letter, `d`: digit, `s`: symbol, `A`: letter or digit
+/// - `\x`: the literal character `x`
+/// - any other non-alphanumeric character is copied as-is
+///
+/// Other letters and digits are rejected so typos don't silently become
+/// literals. For example `LLdd-ssss` gives something like `Qa47-#%!]`.
+pub fn generate_from_pattern(pattern: &str) -> Result<String, GenError> {
+    generate_from_pattern_with_rng(&mut rand::thread_rng(), pattern)
+}
+
+/// Generates a string following `pattern` (see `generate_from_pattern`) from `rng`
+pub fn generate_from_pattern_with_rng(
+    rng: &mut impl RngCore,
+    pattern: &str,
+) -> Result<String, GenError> {
+    let letters = [LOWERCASE, UPPERCASE].concat();
+    let alphanumeric = [LOWERCASE, UPPERCASE, DIGITS].concat();
+    let mut out = String::with_capacity(pattern.len());
+
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        let class: &[u8] = match c {
+            'L' => &letters,
+            'd' => DIGITS,
+            's' => SYMBOLS,
+            'A' => &alphanumeric,
+            '\\' => {
+                out.push(chars.next().ok_or(GenError::DanglingEscape)?);
+                continue;
+            }
+            c if c.is_alphanumeric() => return Err(GenError::UnknownToken(c)),
+            c => {
+                out.push(c);
+                continue;
+            }
+        };
+        out.push(class[random_index(rng, class.len())] as char);
+    }
+
+    Ok(out)
+}
+
+/// Consonants for pronounceable passwords (no `q`, `x` or `y`, which read awkwardly)
+const CONSONANTS: &[u8] = b"bcdfghjklmnprstvwz";
+const VOWELS: &[u8] = b"aeiou";
+
+/// Generates a pronounceable password such as `Kotamuribe42`
+///
+/// Each syllable is a consonant followed by a vowel, so the result is
+/// `2 * syllables` letters plus `append_digits` digits. A syllable adds
+/// about 6.5 bits of entropy, less than random characters, in exchange for
+/// being easy to remember and type.
+pub fn generate_pronounceable(syllables: usize, capitalize: bool, append_digits: usize) -> String {
+    generate_pronounceable_with_rng(
+        &mut rand::thread_rng(),
+        syllables,
+        capitalize,
+        append_digits,
+    )
+}
+
+/// Generates a pronounceable password (see `generate_pronounceable`) from `rng`
+pub fn generate_pronounceable_with_rng(
+    rng: &mut impl RngCore,
+    syllables: usize,
+    capitalize: bool,
+    append_digits: usize,
+) -> String {
+    let mut out = String::with_capacity(syllables * 2 + append_digits);
+
+    for _ in 0..syllables {
+        for class in [CONSONANTS, VOWELS] {
+            out.push(class[random_index(rng, class.len())] as char);
+        }
+    }
+    if capitalize {
+        if let Some(first) = out.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+    }
+    for _ in 0..append_digits {
+        out.push(DIGITS[random_index(rng, DIGITS.len())] as char);
+    }
+
+    out
+}
+
+/// Words of `EFF_LARGE_WORDLIST` without their dice numbers
+fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| {
+        EFF_LARGE_WORDLIST
+            .lines()
+            .filter_map(|line| line.split_whitespace().last())
+            .collect()
+    })
+}
+
+/// Returns a uniformly distributed index in `0..len`
+///
+/// Uses rejection sampling: values from the incomplete last "bucket" of the
+/// u32 range are redrawn, so `% len` doesn't favour low indices.
+fn random_index(rng: &mut impl RngCore, len: usize) -> usize {
+    assert!(len > 0 && len <= u32::MAX as usize, "invalid range length");
+    let len = len as u32;
+    let zone = u32::MAX - (u32::MAX - len + 1) % len;
+
+    loop {
+        let value = rng.next_u32();
+        if value <= zone {
+            return (value % len) as usize;
+        }
     }
-    
-    String::from_utf8(password).unwrap_or_else(|_| generate_random_key(length))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_seeded_generators_are_reproducible() {
+        let opts = PasswordOptions {
+            length: 20,
+            include_symbols: true,
+            require_each_class: true,
+            ..Default::default()
+        };
+        let first = generate_password_with_rng(&mut StdRng::seed_from_u64(42), &opts).unwrap();
+        let second = generate_password_with_rng(&mut StdRng::seed_from_u64(42), &opts).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 20);
+        assert_ne!(
+            first,
+            generate_password_with_rng(&mut StdRng::seed_from_u64(43), &opts).unwrap()
+        );
+
+        let seeded = || StdRng::seed_from_u64(7);
+        assert_eq!(
+            generate_api_key_with_rng(&mut seeded()),
+            generate_api_key_with_rng(&mut seeded())
+        );
+        assert_eq!(
+            generate_passphrase_with_rng(&mut seeded(), 5, "-", true),
+            generate_passphrase_with_rng(&mut seeded(), 5, "-", true)
+        );
+        assert_eq!(
+            generate_from_pattern_with_rng(&mut seeded(), "LLdd-ssss"),
+            generate_from_pattern_with_rng(&mut seeded(), "LLdd-ssss")
+        );
+    }
 
     #[test]
     fn test_generate_random_key_length() {
@@ -79,4 +452,271 @@ mod tests {
         let password = generate_password(16, true);
         assert_eq!(password.len(), 16);
     }
+
+    #[test]
+    fn test_generate_configured_password_uses_config_length() {
+        let length = Config::current().password_options().length;
+        assert_eq!(generate_configured_password().chars().count(), length);
+    }
+
+    #[test]
+    fn test_wordlist_is_eff_large() {
+        let words = wordlist();
+        assert_eq!(words.len(), 7776);
+        assert_eq!(words[0], "abacus");
+        assert_eq!(words[7775], "zoom");
+    }
+
+    #[test]
+    fn test_generate_passphrase() {
+        let passphrase = generate_passphrase(6, "-", false);
+        assert_eq!(passphrase.split('-').count(), 6);
+        assert_eq!(passphrase.matches('-').count(), 5);
+        assert!(passphrase.split('-').all(|w| wordlist().contains(&w)));
+
+        assert_ne!(
+            generate_passphrase(6, "-", false),
+            generate_passphrase(6, "-", false)
+        );
+    }
+
+    #[test]
+    fn test_generate_passphrase_capitalized() {
+        let passphrase = generate_passphrase(4, " ", true);
+        assert_eq!(passphrase.matches(' ').count(), 3);
+        assert!(passphrase
+            .split(' ')
+            .all(|w| w.chars().next().is_some_and(|c| c.is_uppercase())));
+    }
+
+    #[test]
+    fn test_generate_pronounceable_structure() {
+        let password = generate_pronounceable(5, true, 3);
+        assert_eq!(password.len(), 13);
+
+        let (letters, digits) = password.split_at(10);
+        assert!(digits.chars().all(|c| c.is_ascii_digit()));
+        let letters = letters.to_ascii_lowercase();
+        for (i, c) in letters.bytes().enumerate() {
+            let class = if i % 2 == 0 { CONSONANTS } else { VOWELS };
+            assert!(
+                class.contains(&c),
+                "{:?} at {} in {}",
+                c as char,
+                i,
+                password
+            );
+        }
+        assert!(password.starts_with(|c: char| c.is_ascii_uppercase()));
+        assert!(password[1..10].chars().all(|c| c.is_ascii_lowercase()));
+
+        assert_ne!(
+            generate_pronounceable(6, false, 0),
+            generate_pronounceable(6, false, 0)
+        );
+        assert_eq!(generate_pronounceable(0, true, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_generate_password_is_uniform() {
+        // 88 symbols-enabled characters don't divide 2^32, so `% len` would skew
+        let sample = generate_password(200_000, true);
+        let mut counts = std::collections::HashMap::new();
+        for c in sample.chars() {
+            *counts.entry(c).or_insert(0usize) += 1;
+        }
+
+        assert_eq!(counts.len(), 88);
+        let expected = sample.len() as f64 / counts.len() as f64;
+        for (c, count) in counts {
+            let deviation = (count as f64 - expected).abs() / expected;
+            assert!(
+                deviation < 0.15,
+                "'{}' appeared {} times (expected ~{:.0})",
+                c,
+                count,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_random_index_rejects_biased_values() {
+        // An RNG that first returns a value from the biased tail, then a fair one
+        struct Fixed(Vec<u32>);
+        impl RngCore for Fixed {
+            fn next_u32(&mut self) -> u32 {
+                self.0.remove(0)
+            }
+            fn next_u64(&mut self) -> u64 {
+                self.next_u32() as u64
+            }
+            fn fill_bytes(&mut self, _: &mut [u8]) {}
+            fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), rand::Error> {
+                Ok(())
+            }
+        }
+
+        let mut rng = Fixed(vec![u32::MAX, 7]);
+        assert_eq!(random_index(&mut rng, 10), 7);
+        assert!(rng.0.is_empty());
+    }
+
+    #[test]
+    fn test_exclude_ambiguous() {
+        let opts = PasswordOptions {
+            length: 2000,
+            include_symbols: true,
+            exclude_ambiguous: true,
+            ..Default::default()
+        };
+        let password = generate_password_with(&opts).unwrap();
+        assert_eq!(password.len(), 2000);
+        assert!(!password.bytes().any(|c| AMBIGUOUS.contains(&c)));
+    }
+
+    #[test]
+    fn test_require_each_class() {
+        let opts = PasswordOptions {
+            length: 4,
+            include_symbols: true,
+            require_each_class: true,
+            ..Default::default()
+        };
+        for _ in 0..200 {
+            let password = generate_password_with(&opts).unwrap();
+            assert!(
+                password.bytes().any(|c| LOWERCASE.contains(&c)),
+                "{}",
+                password
+            );
+            assert!(
+                password.bytes().any(|c| UPPERCASE.contains(&c)),
+                "{}",
+                password
+            );
+            assert!(
+                password.bytes().any(|c| DIGITS.contains(&c)),
+                "{}",
+                password
+            );
+            assert!(
+                password.bytes().any(|c| SYMBOLS.contains(&c)),
+                "{}",
+                password
+            );
+        }
+
+        let opts = PasswordOptions {
+            include_digits: false,
+            include_uppercase: false,
+            ..opts
+        };
+        let password = generate_password_with(&opts).unwrap();
+        assert!(password
+            .bytes()
+            .all(|c| LOWERCASE.contains(&c) || SYMBOLS.contains(&c)));
+    }
+
+    #[test]
+    fn test_minimum_digits_and_symbols() {
+        let opts = PasswordOptions {
+            length: 6,
+            include_digits: false,
+            min_digits: 2,
+            min_symbols: 1,
+            ..Default::default()
+        };
+        for _ in 0..200 {
+            let password = generate_password_with(&opts).unwrap();
+            assert_eq!(password.len(), 6);
+            assert_eq!(
+                password.bytes().filter(|c| DIGITS.contains(c)).count(),
+                2,
+                "{}",
+                password
+            );
+            assert_eq!(
+                password.bytes().filter(|c| SYMBOLS.contains(c)).count(),
+                1,
+                "{}",
+                password
+            );
+        }
+
+        let opts = PasswordOptions {
+            length: 12,
+            include_symbols: true,
+            min_digits: 3,
+            min_symbols: 4,
+            ..Default::default()
+        };
+        let password = generate_password_with(&opts).unwrap();
+        assert!(password.bytes().filter(|c| DIGITS.contains(c)).count() >= 3);
+        assert!(password.bytes().filter(|c| SYMBOLS.contains(c)).count() >= 4);
+    }
+
+    #[test]
+    fn test_minimums_exceeding_length_are_rejected() {
+        let opts = PasswordOptions {
+            length: 4,
+            min_digits: 3,
+            min_symbols: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_password_with(&opts),
+            Err(GenError::MinimumsExceedLength {
+                required: 5,
+                length: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_generate_from_pattern() {
+        for _ in 0..50 {
+            let value = generate_from_pattern("LLdd-ssss").unwrap();
+            let bytes = value.as_bytes();
+            assert_eq!(bytes.len(), 9);
+            assert!(bytes[..2].iter().all(|b| b.is_ascii_alphabetic()));
+            assert!(bytes[2..4].iter().all(|b| b.is_ascii_digit()));
+            assert_eq!(bytes[4], b'-');
+            assert!(bytes[5..].iter().all(|b| SYMBOLS.contains(b)));
+
+            let value = generate_from_pattern("AAAA\\L.d").unwrap();
+            let bytes = value.as_bytes();
+            assert_eq!(bytes.len(), 7);
+            assert!(bytes[..4].iter().all(|b| b.is_ascii_alphanumeric()));
+            assert_eq!(&bytes[4..6], b"L.");
+            assert!(bytes[6].is_ascii_digit());
+        }
+    }
+
+    #[test]
+    fn test_generate_from_pattern_errors() {
+        assert_eq!(
+            generate_from_pattern("LLx"),
+            Err(GenError::UnknownToken('x'))
+        );
+        assert_eq!(
+            generate_from_pattern("dd9"),
+            Err(GenError::UnknownToken('9'))
+        );
+        assert_eq!(generate_from_pattern("dd\\"), Err(GenError::DanglingEscape));
+        assert_eq!(generate_from_pattern("").unwrap(), "");
+    }
+
+    #[test]
+    fn test_key_entropy() {
+        let (key, bits) = generate_random_key_with_entropy(32);
+        assert_eq!(bits, 256.0);
+        // 43 base64url characters carry at most 6 bits each
+        let estimate = encoded_key_entropy(&key);
+        assert!((256.0..=43.0 * 6.0).contains(&estimate), "{}", estimate);
+
+        assert_eq!(encoded_key_entropy("deadbeef"), 32.0);
+        assert_eq!(encoded_key_entropy("12345678"), 8.0 * 10f64.log2());
+        assert_eq!(encoded_key_entropy(""), 0.0);
+    }
 }
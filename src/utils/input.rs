@@ -2,7 +2,10 @@
 
 use colored::*;
 use rpassword::read_password;
-use std::io::{self, Write};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Environment variable that supplies the master password non-interactively
+pub const MASTER_PASSWORD_ENV: &str = "KOOKIE_MASTER_PASSWORD";
 
 /// Prompts for a password (hidden input)
 pub fn prompt_password(prompt: &str) -> io::Result<String> {
@@ -11,6 +14,36 @@ pub fn prompt_password(prompt: &str) -> io::Result<String> {
     read_password()
 }
 
+/// Reads the master password for scripts as well as interactive use
+///
+/// Uses `KOOKIE_MASTER_PASSWORD` if set, otherwise the first line of stdin
+/// when it is piped, and only prompts when stdin is a terminal.
+pub fn read_master_password() -> io::Result<String> {
+    if master_password_is_prompted() {
+        return prompt_password("Enter master password:");
+    }
+    read_master_password_from(std::env::var(MASTER_PASSWORD_ENV).ok(), io::stdin().lock())
+}
+
+/// Whether `read_master_password` asks the user rather than reading env or stdin
+pub fn master_password_is_prompted() -> bool {
+    io::stdin().is_terminal() && std::env::var_os(MASTER_PASSWORD_ENV).is_none()
+}
+
+/// Non-interactive part of `read_master_password`
+fn read_master_password_from(env: Option<String>, mut input: impl BufRead) -> io::Result<String> {
+    if let Some(password) = env {
+        return Ok(password);
+    }
+    
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "no master password on stdin"));
+    }
+    let password = line.strip_suffix('\n').unwrap_or(&line);
+    Ok(password.strip_suffix('\r').unwrap_or(password).to_string())
+}
+
 /// Prompts for text input
 pub fn prompt_text(prompt: &str) -> io::Result<String> {
     print!("{} ", prompt.cyan());
@@ -98,3 +131,23 @@ pub fn prompt_number(prompt: &str, default: Option<u32>) -> io::Result<u32> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_master_password_from_pipe() {
+        let piped = read_master_password_from(None, "  s3cret pw \r\nignored\n".as_bytes()).unwrap();
+        assert_eq!(piped, "  s3cret pw ");
+
+        let empty = read_master_password_from(None, "".as_bytes());
+        assert_eq!(empty.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_master_password_env_takes_precedence() {
+        let password = read_master_password_from(Some("from-env".into()), "from-pipe\n".as_bytes()).unwrap();
+        assert_eq!(password, "from-env");
+    }
+}
@@ -1,6 +1,9 @@
 //! Utility modules
 
 pub mod clipboard;
+pub mod display;
 pub mod generators;
 pub mod input;
-pub mod display;
+pub mod qr;
+pub mod redact;
+pub mod strength;
@@ -0,0 +1,41 @@
+//! QR codes rendered as terminal text
+//!
+//! Each character cell holds two QR modules stacked vertically using Unicode
+//! half blocks. Colors are inverted so the code scans on dark terminals.
+
+use qrcode::render::unicode::Dense1x2;
+use qrcode::types::QrError;
+use qrcode::QrCode;
+
+/// Renders `data` as a QR code for display in a terminal
+pub fn render_qr_ascii(data: &str) -> Result<String, QrError> {
+    let code = QrCode::new(data.as_bytes())?;
+    Ok(code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .quiet_zone(true)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_typical_otpauth_uri() {
+        let uri = "otpauth://totp/GitHub:alice%40example.com?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&issuer=GitHub&algorithm=SHA1&digits=6&period=30";
+        let rendered = render_qr_ascii(uri).unwrap();
+
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines.len() > 10);
+        // Every row of the code has the same width
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|l| l.chars().count() == width));
+    }
+
+    #[test]
+    fn test_render_rejects_oversized_data() {
+        assert!(render_qr_ascii(&"x".repeat(8000)).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Partial masking of secret values for listings
+//!
+//! A redacted value keeps just enough of its start and end to tell secrets
+//! apart (`sk_l…wxyz`) without revealing them. Values too short to hide a
+//! meaningful middle are masked entirely.
+
+/// Replacement for a fully masked value; its length says nothing about the secret
+pub const MASK: &str = "********";
+
+/// Characters kept at each end by default
+pub const DEFAULT_VISIBLE: usize = 4;
+
+/// Fewest characters that must stay hidden for a partial reveal
+const MIN_HIDDEN: usize = 8;
+
+/// Shows the first and last `visible` characters of `value`, masking the middle
+pub fn redact(value: &str, visible: usize) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if visible == 0 || chars.len() < visible * 2 + MIN_HIDDEN {
+        return MASK.to_string();
+    }
+
+    let head: String = chars[..visible].iter().collect();
+    let tail: String = chars[chars.len() - visible..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_values_fully_masked() {
+        assert_eq!(redact("", 4), MASK);
+        assert_eq!(redact("hunter2", 4), MASK);
+        assert_eq!(redact("abcdefghijklmno", 4), MASK);
+    }
+
+    #[test]
+    fn test_medium_value_shows_ends() {
+        assert_eq!(redact("abcdefghijklmnop", 4), "abcd…mnop");
+        assert_eq!(redact("kk_abcdefghij_xyz", 3), "kk_…xyz");
+    }
+
+    #[test]
+    fn test_long_value_keeps_only_visible_chars() {
+        let key = format!("sk_live_{}", "x".repeat(100));
+        let redacted = redact(&key, DEFAULT_VISIBLE);
+        assert_eq!(redacted, "sk_l…xxxx");
+        assert_eq!(redact(&key, 0), MASK);
+    }
+
+    #[test]
+    fn test_multibyte_characters() {
+        assert_eq!(redact("ééééééééééééééüü", 2), "éé…üü");
+    }
+}
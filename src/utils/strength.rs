@@ -0,0 +1,259 @@
+//! Password strength estimation
+//!
+//! The estimate starts from the classic `length * log2(charset size)` entropy
+//! and then discounts predictable parts of the password: runs of repeated or
+//! sequential characters (`aaaa`, `abcd`, `4321`) and common words.
+
+/// Recommended minimum password length
+pub const RECOMMENDED_LENGTH: usize = 12;
+
+/// Scores below this are reported as weak
+pub const WEAK_SCORE: u8 = 3;
+
+/// Bits credited for a run character or a whole common word
+const PATTERN_BITS: f64 = 1.0;
+const COMMON_WORD_BITS: f64 = 10.0;
+
+/// Passwords and words that show up at the top of every breach list
+const COMMON_WORDS: &[&str] = &[
+    "password", "passw0rd", "qwerty", "letmein", "welcome", "admin", "login", "master", "monkey",
+    "dragon", "iloveyou", "sunshine", "princess", "football", "baseball", "shadow", "secret",
+    "trustno1", "superman", "batman", "hello", "freedom", "whatever", "starwars", "computer",
+    "azerty", "changeme", "default", "root", "test",
+];
+
+/// Result of `estimate_strength`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrengthReport {
+    /// Estimated entropy in bits
+    pub entropy_bits: f64,
+    /// 0 (very weak) to 4 (very strong)
+    pub score: u8,
+    /// Human-readable reasons the password is weaker than it could be
+    pub weaknesses: Vec<String>,
+}
+
+impl StrengthReport {
+    /// True if the score is below `WEAK_SCORE`
+    pub fn is_weak(&self) -> bool {
+        self.score < WEAK_SCORE
+    }
+
+    /// Short label for the score
+    pub fn label(&self) -> &'static str {
+        match self.score {
+            0 => "very weak",
+            1 => "weak",
+            2 => "fair",
+            3 => "strong",
+            _ => "very strong",
+        }
+    }
+}
+
+/// Estimates how hard a password is to guess
+pub fn estimate_strength(password: &str) -> StrengthReport {
+    let chars: Vec<char> = password.chars().collect();
+    let mut weaknesses = Vec::new();
+
+    if chars.len() < RECOMMENDED_LENGTH {
+        weaknesses.push(format!(
+            "Too short ({} characters, at least {} recommended)",
+            chars.len(),
+            RECOMMENDED_LENGTH
+        ));
+    }
+
+    let has_lower = chars.iter().any(|c| c.is_ascii_lowercase());
+    let has_upper = chars.iter().any(|c| c.is_ascii_uppercase());
+    let has_digit = chars.iter().any(|c| c.is_ascii_digit());
+    let has_symbol = chars.iter().any(|c| !c.is_ascii_alphanumeric());
+    if !has_symbol {
+        weaknesses.push("No symbols".to_string());
+    }
+
+    let mut pool = 0u32;
+    for (present, size) in [
+        (has_lower, 26),
+        (has_upper, 26),
+        (has_digit, 10),
+        (has_symbol, 33),
+    ] {
+        if present {
+            pool += size;
+        }
+    }
+    let bits_per_char = f64::from(pool.max(1)).log2();
+
+    // Characters covered by a common word or a run are credited with a
+    // fixed, small amount instead of a full random character
+    let mut predictable = vec![false; chars.len()];
+    let mut entropy_bits = 0.0;
+
+    let lower: Vec<char> = chars.iter().map(|c| c.to_ascii_lowercase()).collect();
+    let mut found_words = Vec::new();
+    for word in COMMON_WORDS {
+        let word: Vec<char> = word.chars().collect();
+        for start in find_all(&lower, &word) {
+            if predictable[start..start + word.len()].iter().any(|&p| p) {
+                continue;
+            }
+            predictable[start..start + word.len()].fill(true);
+            entropy_bits += COMMON_WORD_BITS;
+            let word: String = word.iter().collect();
+            if !found_words.contains(&word) {
+                found_words.push(word);
+            }
+        }
+    }
+    if !found_words.is_empty() {
+        weaknesses.push(format!(
+            "Contains a common word or password ({})",
+            found_words.join(", ")
+        ));
+    }
+
+    let (repeated, sequential) = mark_runs(&chars, &mut predictable, &mut entropy_bits);
+    if repeated {
+        weaknesses.push("Contains repeated characters".to_string());
+    }
+    if sequential {
+        weaknesses.push("Contains a sequence like 'abc' or '123'".to_string());
+    }
+
+    let random_chars = predictable.iter().filter(|&&p| !p).count();
+    entropy_bits += random_chars as f64 * bits_per_char;
+
+    StrengthReport {
+        entropy_bits,
+        score: score(entropy_bits),
+        weaknesses,
+    }
+}
+
+/// Returns true if the password is a common one, ignoring case and any
+/// digits or symbols around it (`Password123!` counts as `password`)
+pub fn is_common(password: &str) -> bool {
+    let core = password
+        .trim_matches(|c: char| !c.is_alphabetic())
+        .to_lowercase();
+    COMMON_WORDS.contains(&core.as_str())
+}
+
+/// Marks runs of 3+ repeated or sequential characters as predictable
+///
+/// The first character of a run keeps full credit; the rest are credited
+/// `PATTERN_BITS` each. Returns whether repeated / sequential runs were seen.
+fn mark_runs(chars: &[char], predictable: &mut [bool], entropy_bits: &mut f64) -> (bool, bool) {
+    let mut repeated = false;
+    let mut sequential = false;
+
+    let mut start = 0;
+    while start + 2 < chars.len() {
+        let step = chars[start + 1] as i64 - chars[start] as i64;
+        if !(-1..=1).contains(&step) {
+            start += 1;
+            continue;
+        }
+
+        let mut end = start + 1;
+        while end + 1 < chars.len() && chars[end + 1] as i64 - chars[end] as i64 == step {
+            end += 1;
+        }
+
+        if end - start >= 2 {
+            if step == 0 {
+                repeated = true;
+            } else {
+                sequential = true;
+            }
+            for flag in &mut predictable[start + 1..=end] {
+                if !*flag {
+                    *flag = true;
+                    *entropy_bits += PATTERN_BITS;
+                }
+            }
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+
+    (repeated, sequential)
+}
+
+fn find_all(haystack: &[char], needle: &[char]) -> Vec<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return Vec::new();
+    }
+    haystack
+        .windows(needle.len())
+        .enumerate()
+        .filter(|(_, window)| *window == needle)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn score(entropy_bits: f64) -> u8 {
+    match entropy_bits {
+        b if b < 28.0 => 0,
+        b if b < 36.0 => 1,
+        b if b < 60.0 => 2,
+        b if b < 80.0 => 3,
+        _ => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generators::generate_password;
+
+    #[test]
+    fn test_is_common() {
+        assert!(is_common("password"));
+        assert!(is_common("Password123!"));
+        assert!(is_common("!!Qwerty"));
+        assert!(!is_common("password-manager"));
+        assert!(!is_common("xk9#mQ2$"));
+    }
+
+    #[test]
+    fn test_common_password_scores_low() {
+        let report = estimate_strength("password");
+        assert_eq!(report.score, 0);
+        assert!(report.weaknesses.iter().any(|w| w.contains("common word")));
+        assert!(report.weaknesses.iter().any(|w| w.starts_with("Too short")));
+        assert!(report.weaknesses.iter().any(|w| w == "No symbols"));
+
+        let report = estimate_strength("root-admin-root");
+        assert!(report
+            .weaknesses
+            .iter()
+            .any(|w| w == "Contains a common word or password (admin, root)"));
+        assert!(report.is_weak());
+    }
+
+    #[test]
+    fn test_patterns_are_detected() {
+        let report = estimate_strength("123456");
+        assert_eq!(report.score, 0);
+        assert!(report.weaknesses.iter().any(|w| w.contains("sequence")));
+
+        let report = estimate_strength("zzzzzzzzzzzzzzzz");
+        assert_eq!(report.score, 0);
+        assert!(report
+            .weaknesses
+            .iter()
+            .any(|w| w == "Contains repeated characters"));
+    }
+
+    #[test]
+    fn test_random_password_scores_high() {
+        for _ in 0..20 {
+            let password = generate_password(24, true);
+            let report = estimate_strength(&password);
+            assert_eq!(report.score, 4, "{}", password);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Rewriting the vault file without leftover history and trash
+//!
+//! `Vault::compact` applies the current retention policy (password history
+//! limit and `TRASH_RETENTION_DAYS`) to data that may have been written under
+//! a looser one, then saves. Every save encrypts with a fresh nonce, so this
+//! doubles as a re-encryption refresh.
+
+use super::trash::{self, TRASH_RETENTION_DAYS};
+use super::{Vault, VaultError};
+use chrono::Duration;
+use std::fs;
+
+/// What `Vault::compact` removed and the vault file size before and after
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactReport {
+    pub old_size: u64,
+    pub new_size: u64,
+    pub history_trimmed: usize,
+    pub trash_purged: usize,
+}
+
+impl Vault {
+    /// Trims password history and expired trash, then rewrites the vault file
+    pub fn compact(&mut self) -> Result<CompactReport, VaultError> {
+        if self.key.is_none() {
+            return Err(VaultError::WrongPassword);
+        }
+        let old_size = fs::metadata(&self.path)?.len();
+
+        let limit = self.password_history_limit();
+        let history_trimmed = self
+            .data
+            .passwords
+            .iter_mut()
+            .map(|p| p.trim_history(limit))
+            .sum();
+        let trash_purged = trash::purge(&mut self.data, Duration::days(TRASH_RETENTION_DAYS));
+
+        self.save()?;
+        let new_size = fs::metadata(&self.path)?.len();
+
+        Ok(CompactReport {
+            old_size,
+            new_size,
+            history_trimmed,
+            trash_purged,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util::test_vault;
+    use crate::vault::types::*;
+    use crate::vault::VaultFile;
+
+    fn encrypted_data(vault: &Vault) -> String {
+        let file: VaultFile = serde_json::from_slice(&fs::read(&vault.path).unwrap()).unwrap();
+        file.encrypted_data
+    }
+
+    #[test]
+    fn test_compact_trims_history_and_shrinks_file() {
+        let (_dir, mut vault) = test_vault();
+        vault.set_password_history_limit(50).unwrap();
+        vault
+            .add_password(Password::new(
+                "github".into(),
+                "v0".into(),
+                None,
+                None,
+                None,
+            ))
+            .unwrap();
+        for i in 1..=30 {
+            let update = PasswordUpdate {
+                password: Some(format!("password-value-{i}")),
+                ..Default::default()
+            };
+            vault.update_password("github", update).unwrap();
+        }
+
+        vault.set_password_history_limit(2).unwrap();
+        let before = encrypted_data(&vault);
+        let report = vault.compact().unwrap();
+
+        assert_eq!(report.history_trimmed, 28);
+        assert_eq!(report.trash_purged, 0);
+        assert!(report.new_size < report.old_size);
+        assert_ne!(encrypted_data(&vault), before);
+
+        let mut reopened = Vault::with_path(vault.path.clone());
+        reopened.unlock("pw").unwrap();
+        let password = reopened.get_password("github").unwrap();
+        assert_eq!(password.password.expose_secret(), "password-value-30");
+        assert_eq!(password.history.len(), 2);
+    }
+
+    #[test]
+    fn test_compact_refreshes_encryption_without_changes() {
+        let (_dir, mut vault) = test_vault();
+        let before = encrypted_data(&vault);
+
+        let report = vault.compact().unwrap();
+
+        assert_eq!(report.history_trimmed, 0);
+        assert_ne!(encrypted_data(&vault), before);
+    }
+
+    #[test]
+    fn test_compact_requires_unlocked_vault() {
+        let (_dir, mut vault) = test_vault();
+        vault.lock();
+        assert!(matches!(vault.compact(), Err(VaultError::WrongPassword)));
+    }
+}
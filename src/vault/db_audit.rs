@@ -0,0 +1,178 @@
+//! Checks for weak or risky database credentials
+//!
+//! Flags passwords that are empty, a well-known default, or the same as the
+//! username, and remote hosts stored without an explicit port (so clients
+//! silently fall back to the default port of whatever driver they use).
+
+use super::types::DbCredential;
+use super::Vault;
+use std::fmt;
+use subtle::ConstantTimeEq;
+
+/// Passwords database images and installers commonly ship with
+const DEFAULT_PASSWORDS: &[&str] = &[
+    "postgres", "root", "admin", "password", "mysql", "sa", "changeme",
+];
+
+/// Something wrong with a database credential
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbIssue {
+    EmptyPassword,
+    DefaultPassword,
+    PasswordIsUsername,
+    RemoteHostWithoutPort,
+}
+
+impl fmt::Display for DbIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DbIssue::EmptyPassword => write!(f, "password is empty"),
+            DbIssue::DefaultPassword => write!(f, "password is a well-known default"),
+            DbIssue::PasswordIsUsername => write!(f, "password is the same as the username"),
+            DbIssue::RemoteHostWithoutPort => write!(f, "remote host has no port set"),
+        }
+    }
+}
+
+/// Issues found for one database credential
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbAuditFinding {
+    pub id: String,
+    pub name: String,
+    pub issues: Vec<DbIssue>,
+}
+
+impl Vault {
+    /// Returns the database credentials with weak passwords or risky settings
+    ///
+    /// Credentials without issues are left out.
+    pub fn audit_db_credentials(&self) -> Vec<DbAuditFinding> {
+        self.data
+            .db_credentials
+            .iter()
+            .filter_map(|cred| {
+                let issues = db_issues(cred);
+                (!issues.is_empty()).then(|| DbAuditFinding {
+                    id: cred.id.clone(),
+                    name: cred.name.clone(),
+                    issues,
+                })
+            })
+            .collect()
+    }
+}
+
+fn db_issues(cred: &DbCredential) -> Vec<DbIssue> {
+    let mut issues = Vec::new();
+
+    let password = cred.password.expose_secret().as_bytes();
+    if password.is_empty() {
+        issues.push(DbIssue::EmptyPassword);
+    } else if DEFAULT_PASSWORDS
+        .iter()
+        .any(|default| eq_ignore_case_ct(password, default.as_bytes()))
+    {
+        issues.push(DbIssue::DefaultPassword);
+    } else if eq_ignore_case_ct(password, cred.username.as_bytes()) {
+        issues.push(DbIssue::PasswordIsUsername);
+    }
+
+    if cred.port.is_none() && !is_local_host(&cred.host) {
+        issues.push(DbIssue::RemoteHostWithoutPort);
+    }
+
+    issues
+}
+
+/// ASCII case-insensitive comparison without lowercased copies of the secret
+///
+/// Runs in time independent of where the inputs differ.
+fn eq_ignore_case_ct(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter()
+        .zip(b)
+        .fold(subtle::Choice::from(1), |acc, (x, y)| {
+            acc & x.to_ascii_lowercase().ct_eq(&y.to_ascii_lowercase())
+        })
+        .into()
+}
+
+/// True for loopback addresses and Unix socket paths
+fn is_local_host(host: &str) -> bool {
+    let host = host.trim().trim_start_matches('[').trim_end_matches(']');
+    host.is_empty()
+        || host.eq_ignore_ascii_case("localhost")
+        || host.starts_with("127.")
+        || host == "::1"
+        || host.starts_with('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+
+    fn cred(
+        name: &str,
+        host: &str,
+        port: Option<u16>,
+        username: &str,
+        password: &str,
+    ) -> DbCredential {
+        DbCredential::new(
+            name.into(),
+            host.into(),
+            port,
+            "app".into(),
+            username.into(),
+            password.into(),
+            Some("postgres".into()),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_audit_flags_weak_credentials() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        vault.data.db_credentials = vec![
+            cred("default", "db.example.com", None, "app", "Postgres"),
+            cred("empty", "localhost", None, "app", ""),
+            cred("same", "127.0.0.1", Some(5432), "deploy", "deploy"),
+            cred("safe", "db.example.com", Some(5432), "app", "x9!Tq2#vLm8@"),
+            cred("local", "[::1]", None, "app", "x9!Tq2#vLm8@"),
+        ];
+
+        let findings = vault.audit_db_credentials();
+        let issues: Vec<(&str, &[DbIssue])> = findings
+            .iter()
+            .map(|f| (f.name.as_str(), f.issues.as_slice()))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (
+                    "default",
+                    &[DbIssue::DefaultPassword, DbIssue::RemoteHostWithoutPort][..]
+                ),
+                ("empty", &[DbIssue::EmptyPassword][..]),
+                ("same", &[DbIssue::PasswordIsUsername][..]),
+            ]
+        );
+        assert_eq!(findings[0].id, vault.data.db_credentials[0].id);
+    }
+
+    #[test]
+    fn test_audit_passes_safe_credential() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        vault.data.db_credentials = vec![cred(
+            "prod",
+            "db.example.com",
+            Some(5432),
+            "app",
+            "x9!Tq2#vLm8@",
+        )];
+        assert!(vault.audit_db_credentials().is_empty());
+    }
+}
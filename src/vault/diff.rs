@@ -0,0 +1,165 @@
+//! Compares the secrets of two vaults by name
+//!
+//! Meant for troubleshooting sync: the diff says which secrets differ, never
+//! what their values are.
+
+use super::readonly::ReadOnlyVault;
+use super::transfer::same_content;
+use super::types::*;
+use super::VaultData;
+
+/// A secret present in both vaults whose contents or timestamp differ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedSecret {
+    pub secret_type: SecretType,
+    pub name: String,
+    /// Anything besides ids, timestamps and usage counters differs
+    pub value_changed: bool,
+    pub updated_at_changed: bool,
+}
+
+/// Differences between two vaults
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VaultDiff {
+    /// Secrets only the first vault has
+    pub only_in_self: Vec<(SecretType, String)>,
+    /// Secrets only the second vault has
+    pub only_in_other: Vec<(SecretType, String)>,
+    pub changed: Vec<ChangedSecret>,
+}
+
+impl VaultDiff {
+    /// True if both vaults hold the same secrets
+    pub fn is_empty(&self) -> bool {
+        self.only_in_self.is_empty() && self.only_in_other.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl VaultData {
+    /// Compares this vault's secrets against `other`'s, matching them by type and name
+    pub fn diff(&self, other: &VaultData) -> VaultDiff {
+        let mut diff = VaultDiff::default();
+        diff_collection(&self.passwords, &other.passwords, &mut diff);
+        diff_collection(&self.api_keys, &other.api_keys, &mut diff);
+        diff_collection(&self.notes, &other.notes, &mut diff);
+        diff_collection(&self.db_credentials, &other.db_credentials, &mut diff);
+        diff_collection(&self.tokens, &other.tokens, &mut diff);
+        diff_collection(&self.totps, &other.totps, &mut diff);
+        diff_collection(&self.ssh_keys, &other.ssh_keys, &mut diff);
+        diff_collection(&self.credit_cards, &other.credit_cards, &mut diff);
+        diff_collection(&self.wifi_credentials, &other.wifi_credentials, &mut diff);
+        diff
+    }
+}
+
+impl ReadOnlyVault {
+    /// Compares this vault's secrets against `other`'s (see `VaultData::diff`)
+    pub fn diff(&self, other: &ReadOnlyVault) -> VaultDiff {
+        self.data().diff(other.data())
+    }
+}
+
+fn diff_collection<T: SecretEntry + Clone + PartialEq>(
+    ours: &[T],
+    theirs: &[T],
+    diff: &mut VaultDiff,
+) {
+    for item in ours {
+        let entry = (item.secret_type(), item.name().to_string());
+        match theirs.iter().find(|t| t.name() == item.name()) {
+            None => diff.only_in_self.push(entry),
+            Some(other) => {
+                let value_changed = !same_content(item, other);
+                let updated_at_changed = item.updated_at() != other.updated_at();
+                if value_changed || updated_at_changed {
+                    diff.changed.push(ChangedSecret {
+                        secret_type: entry.0,
+                        name: entry.1,
+                        value_changed,
+                        updated_at_changed,
+                    });
+                }
+            }
+        }
+    }
+    for item in theirs {
+        if !ours.iter().any(|o| o.name() == item.name()) {
+            diff.only_in_other
+                .push((item.secret_type(), item.name().to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed() {
+        let mut ours = VaultData::default();
+        ours.passwords.push(Password::new(
+            "github".into(),
+            "pw".into(),
+            None,
+            None,
+            None,
+        ));
+        ours.passwords.push(Password::new(
+            "gitlab".into(),
+            "pw".into(),
+            None,
+            None,
+            None,
+        ));
+        ours.notes.push(Note::new("memo".into(), "text".into()));
+        ours.tokens
+            .push(Token::new("ci".into(), "t0k3n".into(), None, None, None));
+
+        let mut theirs = ours.clone();
+        // Same name, different value
+        theirs.passwords[0].password = "hunter2".into();
+        // Same value, touched later
+        theirs.notes[0].updated_at += Duration::seconds(5);
+        // Bookkeeping fields alone don't count
+        theirs.tokens[0].id = "other-id".into();
+        theirs.tokens[0].access_count = 3;
+        theirs.passwords.remove(1);
+        theirs
+            .api_keys
+            .push(ApiKey::new("stripe".into(), "sk".into(), None, None));
+
+        let diff = ours.diff(&theirs);
+        assert_eq!(
+            diff.only_in_self,
+            vec![(SecretType::Password, "gitlab".to_string())]
+        );
+        assert_eq!(
+            diff.only_in_other,
+            vec![(SecretType::ApiKey, "stripe".to_string())]
+        );
+        assert_eq!(
+            diff.changed,
+            vec![
+                ChangedSecret {
+                    secret_type: SecretType::Password,
+                    name: "github".into(),
+                    value_changed: true,
+                    updated_at_changed: false,
+                },
+                ChangedSecret {
+                    secret_type: SecretType::Note,
+                    name: "memo".into(),
+                    value_changed: false,
+                    updated_at_changed: true,
+                },
+            ]
+        );
+
+        // Flags only: no secret value leaks into the diff
+        let debug = format!("{:?}", diff);
+        assert!(!debug.contains("hunter2") && !debug.contains("t0k3n"));
+
+        assert!(ours.diff(&ours.clone()).is_empty());
+    }
+}
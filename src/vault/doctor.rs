@@ -0,0 +1,455 @@
+//! Aggregate vault health diagnostics
+//!
+//! `Vault::doctor` runs a series of independent checks over the unlocked vault
+//! and collects their results into a single report. A failing check never
+//! prevents the remaining checks from running.
+
+use super::{migrate, storage, Vault, VaultData, VaultError};
+use crate::crypto;
+use crate::utils::strength;
+
+/// Share of the size limit (in percent) from which the vault size is reported
+const QUOTA_WARN_PERCENT: usize = 80;
+
+/// Outcome of a single diagnostic check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Result of a single diagnostic check
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub details: Vec<String>,
+}
+
+impl CheckResult {
+    fn pass(name: &'static str) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            details: Vec::new(),
+        }
+    }
+
+    /// Builds a result that passes when there are no findings
+    fn from_findings(name: &'static str, status: CheckStatus, details: Vec<String>) -> Self {
+        if details.is_empty() {
+            Self::pass(name)
+        } else {
+            Self {
+                name,
+                status,
+                details,
+            }
+        }
+    }
+}
+
+/// Report produced by `Vault::doctor`
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl DoctorReport {
+    /// Returns the checks with the given status
+    pub fn with_status(&self, status: CheckStatus) -> Vec<&CheckResult> {
+        self.checks.iter().filter(|c| c.status == status).collect()
+    }
+
+    /// Returns the result of the named check
+    pub fn check(&self, name: &str) -> Option<&CheckResult> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+
+    /// Returns true if every check passed
+    pub fn is_healthy(&self) -> bool {
+        self.checks.iter().all(|c| c.status == CheckStatus::Pass)
+    }
+
+    /// Returns true if any check failed
+    pub fn has_failures(&self) -> bool {
+        self.checks.iter().any(|c| c.status == CheckStatus::Fail)
+    }
+}
+
+impl Vault {
+    /// Runs all health checks against the vault
+    pub fn doctor(&self) -> DoctorReport {
+        DoctorReport {
+            checks: vec![
+                check_vault_file(self),
+                check_crypto(self),
+                check_integrity(self),
+                check_weak_passwords(&self.data),
+                check_reused_passwords(self),
+                check_expired_tokens(self),
+                check_db_credentials(self),
+                check_validation(&self.data),
+                check_quota(self),
+            ],
+        }
+    }
+}
+
+/// Verifies the vault file can be read and parsed
+fn check_vault_file(vault: &Vault) -> CheckResult {
+    const NAME: &str = "vault file";
+
+    if !vault.exists() {
+        return CheckResult::from_findings(
+            NAME,
+            CheckStatus::Fail,
+            vec![format!("No vault file at {}", vault.path.display())],
+        );
+    }
+
+    match storage::load_vault_file(&vault.path).and_then(migrate::migrate) {
+        Ok(_) => CheckResult::pass(NAME),
+        Err(e) => CheckResult::from_findings(NAME, CheckStatus::Fail, vec![e.to_string()]),
+    }
+}
+
+/// Encrypts and decrypts a random payload with the vault's cipher and a throwaway key
+fn check_crypto(vault: &Vault) -> CheckResult {
+    const NAME: &str = "crypto self-test";
+
+    let algorithm = vault.algorithm();
+    let key: [u8; 32] = rand::random();
+    let payload: [u8; 32] = rand::random();
+
+    let result = crypto::encrypt_with(algorithm, &key, &payload)
+        .and_then(|ciphertext| crypto::decrypt_with(algorithm, &key, &ciphertext));
+
+    match result {
+        Ok(decrypted) if decrypted == payload => CheckResult::pass(NAME),
+        Ok(_) => CheckResult::from_findings(
+            NAME,
+            CheckStatus::Fail,
+            vec![format!(
+                "{}: decrypted payload does not match the original",
+                algorithm
+            )],
+        ),
+        Err(e) => CheckResult::from_findings(
+            NAME,
+            CheckStatus::Fail,
+            vec![format!("{}: {}", algorithm, e)],
+        ),
+    }
+}
+
+/// Reports structural problems found by `Vault::verify_integrity`
+fn check_integrity(vault: &Vault) -> CheckResult {
+    let issues = vault
+        .verify_integrity()
+        .issues
+        .iter()
+        .map(|issue| issue.to_string())
+        .collect();
+
+    CheckResult::from_findings("integrity", CheckStatus::Fail, issues)
+}
+
+/// Flags passwords the strength estimator considers easy to guess
+fn check_weak_passwords(data: &VaultData) -> CheckResult {
+    let weak = data
+        .passwords
+        .iter()
+        .filter_map(|p| {
+            let report = strength::estimate_strength(p.password.expose_secret());
+            report.is_weak().then(|| {
+                format!(
+                    "Password '{}' is {} ({:.0} bits)",
+                    p.name,
+                    report.label(),
+                    report.entropy_bits
+                )
+            })
+        })
+        .collect();
+
+    CheckResult::from_findings("weak passwords", CheckStatus::Warn, weak)
+}
+
+/// Flags password values shared by more than one entry
+fn check_reused_passwords(vault: &Vault) -> CheckResult {
+    let reused = vault
+        .reused_passwords()
+        .into_iter()
+        .map(|(_, names)| format!("Same password used by: {}", names.join(", ")))
+        .collect();
+
+    CheckResult::from_findings("reused passwords", CheckStatus::Warn, reused)
+}
+
+/// Flags tokens whose expiry date has passed
+fn check_expired_tokens(vault: &Vault) -> CheckResult {
+    let expired = vault
+        .expired_tokens()
+        .into_iter()
+        .map(|t| format!("Token '{}' has expired", t.name))
+        .collect();
+
+    CheckResult::from_findings("expired tokens", CheckStatus::Warn, expired)
+}
+
+/// Flags database credentials with weak passwords or risky settings
+fn check_db_credentials(vault: &Vault) -> CheckResult {
+    let findings = vault
+        .audit_db_credentials()
+        .into_iter()
+        .map(|finding| {
+            let issues: Vec<String> = finding.issues.iter().map(|i| i.to_string()).collect();
+            format!("Database '{}': {}", finding.name, issues.join(", "))
+        })
+        .collect();
+
+    CheckResult::from_findings("database credentials", CheckStatus::Warn, findings)
+}
+
+/// Flags secrets that no longer pass the checks applied when adding them
+///
+/// Covers cards (Luhn checksum, expiry), TOTP secrets that can't produce a
+/// code, and passwords that break the vault's password policy.
+fn check_validation(data: &VaultData) -> CheckResult {
+    let now = chrono::Utc::now();
+    let mut findings = Vec::new();
+
+    for card in &data.credit_cards {
+        if let Err(e) = card.validate_at(now) {
+            findings.push(format!("Card '{}': {}", card.name, e));
+        }
+    }
+    for totp in &data.totps {
+        if let Err(e) = totp.current_code(now) {
+            findings.push(format!("TOTP '{}': {}", totp.name, e));
+        }
+    }
+    if let Some(policy) = &data.policy {
+        for p in &data.passwords {
+            if let Err(VaultError::PolicyViolation(reasons)) =
+                policy.check(p.password.expose_secret())
+            {
+                findings.push(format!(
+                    "Password '{}' breaks the vault policy: {}",
+                    p.name,
+                    reasons.join("; ")
+                ));
+            }
+        }
+    }
+
+    CheckResult::from_findings("validation", CheckStatus::Warn, findings)
+}
+
+/// Compares the serialized vault size with the save limit
+fn check_quota(vault: &Vault) -> CheckResult {
+    const NAME: &str = "vault size";
+
+    let size = match serde_json::to_vec(&vault.data) {
+        Ok(json) => zeroize::Zeroizing::new(json).len(),
+        Err(e) => return CheckResult::from_findings(NAME, CheckStatus::Fail, vec![e.to_string()]),
+    };
+    let limit = vault.size_limit;
+    let detail = format!("{} of {} bytes used", size, limit);
+
+    if size > limit {
+        CheckResult::from_findings(NAME, CheckStatus::Fail, vec![detail])
+    } else if size * 100 >= limit * QUOTA_WARN_PERCENT {
+        CheckResult::from_findings(NAME, CheckStatus::Warn, vec![detail])
+    } else {
+        CheckResult::pass(NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use crate::vault::types::*;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_doctor_reports_each_problem_independently() {
+        // The temp dir is empty, so there is no vault file
+        let (_dir, mut vault) = test_util::empty_vault();
+
+        let mut weak = Password::new("weak".into(), "abc".into(), None, None, None);
+        weak.updated_at = weak.created_at - Duration::days(1);
+        let reused_a = Password::new(
+            "reused-a".into(),
+            "correct horse battery".into(),
+            None,
+            None,
+            None,
+        );
+        let reused_b = Password::new(
+            "reused-b".into(),
+            "correct horse battery".into(),
+            None,
+            None,
+            None,
+        );
+        let expired = Token::new(
+            "old-token".into(),
+            "tok".into(),
+            None,
+            None,
+            Some(Utc::now() - Duration::days(1)),
+        );
+
+        vault.data.passwords = vec![weak, reused_a, reused_b];
+        vault.data.tokens = vec![expired];
+
+        let report = vault.doctor();
+
+        assert_eq!(
+            report.check("vault file").unwrap().status,
+            CheckStatus::Fail
+        );
+        assert_eq!(
+            report.check("crypto self-test").unwrap().status,
+            CheckStatus::Pass
+        );
+        assert_eq!(report.check("integrity").unwrap().status, CheckStatus::Fail);
+        assert_eq!(
+            report.check("weak passwords").unwrap().status,
+            CheckStatus::Warn
+        );
+        assert_eq!(
+            report.check("reused passwords").unwrap().status,
+            CheckStatus::Warn
+        );
+        assert_eq!(
+            report.check("expired tokens").unwrap().status,
+            CheckStatus::Warn
+        );
+
+        let reused = &report.check("reused passwords").unwrap().details;
+        assert_eq!(reused.len(), 1);
+        assert!(!reused[0].contains("correct horse battery"));
+
+        assert!(report.has_failures());
+        assert!(!report.is_healthy());
+    }
+
+    #[test]
+    fn test_crypto_check_uses_the_vault_cipher() {
+        for algorithm in crypto::Algorithm::ALL {
+            let (_dir, mut vault) = test_util::empty_vault();
+            vault.set_algorithm(algorithm);
+            assert_eq!(check_crypto(&vault).status, CheckStatus::Pass);
+        }
+    }
+
+    #[test]
+    fn test_doctor_clean_data_passes_data_checks() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        vault.data.passwords = vec![Password::new(
+            "ok".into(),
+            "a-long-unique-pw".into(),
+            None,
+            None,
+            None,
+        )];
+
+        let report = vault.doctor();
+
+        for name in [
+            "crypto self-test",
+            "integrity",
+            "weak passwords",
+            "reused passwords",
+            "expired tokens",
+            "validation",
+            "vault size",
+        ] {
+            assert_eq!(
+                report.check(name).unwrap().status,
+                CheckStatus::Pass,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_doctor_reports_vault_size() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        vault.data.notes = vec![Note::new("big".into(), "x".repeat(900))];
+        let size = serde_json::to_vec(&vault.data).unwrap().len();
+
+        vault.set_size_limit(size * 2);
+        assert_eq!(
+            vault.doctor().check("vault size").unwrap().status,
+            CheckStatus::Pass
+        );
+
+        vault.set_size_limit(size + 10);
+        let report = vault.doctor();
+        let check = report.check("vault size").unwrap();
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert_eq!(
+            check.details,
+            vec![format!("{} of {} bytes used", size, size + 10)]
+        );
+
+        vault.set_size_limit(size - 1);
+        assert_eq!(
+            vault.doctor().check("vault size").unwrap().status,
+            CheckStatus::Fail
+        );
+    }
+
+    #[test]
+    fn test_doctor_reports_invalid_secrets() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        vault.data.credit_cards = vec![
+            CreditCard::new(
+                "expired".into(),
+                "A".into(),
+                "4111 1111 1111 1111".into(),
+                1,
+                2020,
+                "123".into(),
+                None,
+            ),
+            CreditCard::new(
+                "valid".into(),
+                "A".into(),
+                "4111 1111 1111 1111".into(),
+                12,
+                2099,
+                "123".into(),
+                None,
+            ),
+        ];
+        vault.data.totps = vec![
+            Totp::new("broken".into(), "not base32!".into(), None),
+            Totp::new("ok".into(), "JBSWY3DPEHPK3PXP".into(), None),
+        ];
+        vault.data.policy = Some(crate::vault::policy::PasswordPolicy {
+            min_length: 12,
+            ..Default::default()
+        });
+        vault.data.passwords = vec![
+            Password::new("short".into(), "hunter2".into(), None, None, None),
+            Password::new("long".into(), "a-long-unique-pw".into(), None, None, None),
+        ];
+
+        let report = vault.doctor();
+        let check = report.check("validation").unwrap();
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert_eq!(check.details.len(), 3, "{:?}", check.details);
+        assert!(check.details[0].starts_with("Card 'expired'"));
+        assert!(check.details[1].starts_with("TOTP 'broken'"));
+        assert!(check.details[2].starts_with("Password 'short'"));
+        assert!(!check.details[2].contains("hunter2"));
+    }
+}
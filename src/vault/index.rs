@@ -0,0 +1,206 @@
+//! Optional lookup index behind `get_*_by_id` and `get_*_by_name`
+//!
+//! Lookups scan the collection by default. A long-running process that looks
+//! up secrets repeatedly can enable the index with `Vault::with_index`: it
+//! maps ids and names to positions in each collection, is built on the first
+//! lookup and dropped whenever the vault is saved or reloaded.
+//!
+//! Changes made through `data` directly don't drop the index. Hits are
+//! checked against the collection and a collection whose length changed is
+//! re-indexed, so such changes never return the wrong secret; renaming a
+//! secret in place only shows up after the next save.
+
+use super::types::{SecretEntry, SecretType};
+use super::{Vault, VaultData};
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
+
+/// Field a lookup matches on
+#[derive(Debug, Clone, Copy)]
+pub(super) enum LookupKey {
+    Id,
+    Name,
+}
+
+impl LookupKey {
+    fn matches<T: SecretEntry>(self, item: &T, key: &str) -> bool {
+        match self {
+            LookupKey::Id => item.id() == key,
+            LookupKey::Name => item.name() == key,
+        }
+    }
+}
+
+/// Positions within one collection
+#[derive(Default)]
+struct Positions {
+    by_id: HashMap<String, usize>,
+    by_name: HashMap<String, usize>,
+    len: usize,
+}
+
+/// Positions of every secret, per type
+#[derive(Default)]
+pub struct SecretIndex {
+    positions: HashMap<SecretType, Positions>,
+}
+
+impl SecretIndex {
+    fn build(data: &VaultData) -> Self {
+        let mut index = Self::default();
+        for entry in data.entries() {
+            let positions = index.positions.entry(entry.secret_type()).or_default();
+            // Like a scan, the first secret with a given id or name wins
+            positions
+                .by_id
+                .entry(entry.id().to_string())
+                .or_insert(positions.len);
+            positions
+                .by_name
+                .entry(entry.name().to_string())
+                .or_insert(positions.len);
+            positions.len += 1;
+        }
+        index
+    }
+
+    /// Looks `key` up in `items`, or `None` if this index is out of date for them
+    fn resolve<'a, T: SecretEntry>(
+        &self,
+        items: &'a [T],
+        key: &str,
+        by: LookupKey,
+    ) -> Option<Option<&'a T>> {
+        let Some(first) = items.first() else {
+            return Some(None);
+        };
+        let positions = self.positions.get(&first.secret_type())?;
+        if positions.len != items.len() {
+            return None;
+        }
+        let map = match by {
+            LookupKey::Id => &positions.by_id,
+            LookupKey::Name => &positions.by_name,
+        };
+        match map.get(key) {
+            None => Some(None),
+            Some(&idx) => items
+                .get(idx)
+                .filter(|item| by.matches(*item, key))
+                .map(Some),
+        }
+    }
+}
+
+impl Vault {
+    /// Backs `get_*` lookups with an index (see `vault::index`)
+    pub fn with_index(mut self) -> Self {
+        self.index = Some(RwLock::new(None));
+        self
+    }
+
+    /// Drops the index so the next lookup rebuilds it
+    pub(super) fn invalidate_index(&self) {
+        if let Some(index) = &self.index {
+            *index.write().unwrap_or_else(PoisonError::into_inner) = None;
+        }
+    }
+
+    /// Finds a secret in one of this vault's collections by id or name
+    pub(super) fn lookup<'a, T: SecretEntry>(
+        &self,
+        items: &'a [T],
+        key: &str,
+        by: LookupKey,
+    ) -> Option<&'a T> {
+        let scan = || items.iter().find(|item| by.matches(*item, key));
+        let Some(cell) = &self.index else {
+            return scan();
+        };
+
+        let cached = cell
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .and_then(|index| index.resolve(items, key, by));
+        if let Some(found) = cached {
+            return found;
+        }
+
+        let mut guard = cell.write().unwrap_or_else(PoisonError::into_inner);
+        guard
+            .insert(SecretIndex::build(&self.data))
+            .resolve(items, key, by)
+            .unwrap_or_else(scan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use crate::vault::types::{Note, Password};
+
+    fn test_vault() -> (tempfile::TempDir, Vault) {
+        let (dir, vault) = test_util::test_vault();
+        (dir, vault.with_index())
+    }
+
+    fn add(vault: &mut Vault, name: &str) -> String {
+        let password = Password::new(name.into(), "pw".into(), None, None, None);
+        let id = password.id.clone();
+        vault.add_password(password).unwrap();
+        id
+    }
+
+    #[test]
+    fn test_index_follows_add_delete_and_rename() {
+        let (_dir, mut vault) = test_vault();
+        let github = add(&mut vault, "github");
+        let gitlab = add(&mut vault, "gitlab");
+        let aws = add(&mut vault, "aws");
+        assert_eq!(vault.get_password("gitlab").unwrap().id, gitlab);
+        assert!(vault.index.as_ref().unwrap().read().unwrap().is_some());
+
+        // Deleting shifts later positions
+        vault.delete_password("github").unwrap();
+        assert!(vault.get_password("github").is_none());
+        assert!(vault.get_password_by_id(&github).is_none());
+        assert_eq!(vault.get_password("gitlab").unwrap().id, gitlab);
+        assert_eq!(vault.get_password_by_id(&aws).unwrap().name, "aws");
+
+        vault
+            .rename_secret(SecretType::Password, "gitlab", "gitlab-work")
+            .unwrap();
+        assert!(vault.get_password_by_name("gitlab").is_none());
+        assert_eq!(vault.get_password("gitlab-work").unwrap().id, gitlab);
+
+        let heroku = add(&mut vault, "heroku");
+        assert_eq!(vault.get_password("heroku").unwrap().id, heroku);
+        // Other collections are indexed separately
+        assert!(vault.get_note("heroku").is_none());
+    }
+
+    #[test]
+    fn test_index_survives_direct_data_changes() {
+        let (_dir, mut vault) = test_vault();
+        add(&mut vault, "github");
+        add(&mut vault, "gitlab");
+        assert!(vault.get_password("gitlab").is_some());
+
+        // Same length, different order: hits are verified
+        vault.data.passwords.swap(0, 1);
+        assert_eq!(vault.get_password("github").unwrap().name, "github");
+        assert_eq!(vault.get_password("gitlab").unwrap().name, "gitlab");
+
+        // Different length: the collection is re-indexed
+        vault.data.passwords.remove(0);
+        vault
+            .data
+            .notes
+            .push(Note::new("memo".into(), "text".into()));
+        assert!(vault.get_password("gitlab").is_none());
+        assert_eq!(vault.get_password("github").unwrap().name, "github");
+        assert_eq!(vault.get_note("memo").unwrap().name, "memo");
+    }
+}
@@ -0,0 +1,264 @@
+//! Structural checks over decrypted vault data
+//!
+//! `Vault::verify_integrity` looks for data that can't be produced through the
+//! normal API but may appear after hand-editing, a bad import or a bug:
+//! duplicate ids or names, empty required values, ids that aren't UUIDs and
+//! timestamps that run backwards.
+
+use super::types::*;
+use super::{Vault, VaultData};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Kind of problem found in a single secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityIssueKind {
+    /// Another secret in the same collection has this id
+    DuplicateId,
+    /// Another secret in the same collection has this name
+    DuplicateName,
+    /// A required field (named) is empty
+    EmptyField(&'static str),
+    /// The id is not a valid UUID
+    MalformedId,
+    /// `updated_at` is earlier than `created_at`
+    UpdatedBeforeCreated,
+}
+
+/// A problem found in a single secret
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityIssue {
+    pub secret_type: SecretType,
+    pub id: String,
+    pub name: String,
+    pub kind: IntegrityIssueKind,
+}
+
+impl std::fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = self.secret_type;
+        match self.kind {
+            IntegrityIssueKind::DuplicateId => write!(f, "Duplicate {} id: {}", kind, self.id),
+            IntegrityIssueKind::DuplicateName => {
+                write!(f, "Duplicate {} name: {} ({})", kind, self.name, self.id)
+            }
+            IntegrityIssueKind::EmptyField(field) => write!(
+                f,
+                "{} '{}' ({}) has an empty {}",
+                kind, self.name, self.id, field
+            ),
+            IntegrityIssueKind::MalformedId => {
+                write!(
+                    f,
+                    "{} '{}' has a malformed id: {}",
+                    kind, self.name, self.id
+                )
+            }
+            IntegrityIssueKind::UpdatedBeforeCreated => write!(
+                f,
+                "{} '{}' ({}) was updated before it was created",
+                kind, self.name, self.id
+            ),
+        }
+    }
+}
+
+/// Result of `Vault::verify_integrity`
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    /// Returns true if no issues were found
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Vault {
+    /// Scans the loaded data for structural problems
+    pub fn verify_integrity(&self) -> IntegrityReport {
+        verify_data(&self.data)
+    }
+}
+
+pub(crate) fn verify_data(data: &VaultData) -> IntegrityReport {
+    let mut issues = Vec::new();
+
+    check_collection(&mut issues, &data.passwords, |p| {
+        p.password.is_empty().then_some("password")
+    });
+    check_collection(&mut issues, &data.api_keys, |k| {
+        k.key.is_empty().then_some("key")
+    });
+    check_collection(&mut issues, &data.notes, |n| {
+        n.content.is_empty().then_some("content")
+    });
+    check_collection(&mut issues, &data.db_credentials, |c| {
+        if c.host.is_empty() {
+            Some("host")
+        } else {
+            c.password.is_empty().then_some("password")
+        }
+    });
+    check_collection(&mut issues, &data.tokens, |t| {
+        t.token.is_empty().then_some("token")
+    });
+    check_collection(&mut issues, &data.totps, |t| {
+        t.secret.is_empty().then_some("secret")
+    });
+    check_collection(&mut issues, &data.ssh_keys, |k| {
+        k.private_key.is_empty().then_some("private key")
+    });
+    check_collection(&mut issues, &data.credit_cards, |c| {
+        c.number.is_empty().then_some("card number")
+    });
+    check_collection(&mut issues, &data.wifi_credentials, |w| {
+        w.ssid.is_empty().then_some("ssid")
+    });
+
+    IntegrityReport { issues }
+}
+
+/// Checks one collection; `empty_field` names the first empty required field
+fn check_collection<T: SecretEntry>(
+    issues: &mut Vec<IntegrityIssue>,
+    items: &[T],
+    empty_field: impl Fn(&T) -> Option<&'static str>,
+) {
+    let mut ids = HashSet::new();
+    let mut names = HashSet::new();
+
+    for item in items {
+        let mut report = |kind| {
+            issues.push(IntegrityIssue {
+                secret_type: item.secret_type(),
+                id: item.id().to_string(),
+                name: item.name().to_string(),
+                kind,
+            })
+        };
+
+        if !ids.insert(item.id()) {
+            report(IntegrityIssueKind::DuplicateId);
+        }
+        if !names.insert(item.name()) {
+            report(IntegrityIssueKind::DuplicateName);
+        }
+        if Uuid::parse_str(item.id()).is_err() {
+            report(IntegrityIssueKind::MalformedId);
+        }
+        if let Some(field) = empty_field(item) {
+            report(IntegrityIssueKind::EmptyField(field));
+        }
+        if item.updated_at() < item.created_at() {
+            report(IntegrityIssueKind::UpdatedBeforeCreated);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use chrono::Duration;
+
+    fn kinds(vault: &Vault) -> Vec<(String, IntegrityIssueKind)> {
+        vault
+            .verify_integrity()
+            .issues
+            .into_iter()
+            .map(|i| (i.id, i.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_vault_has_no_issues() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        vault.data.passwords.push(Password::new(
+            "github".into(),
+            "pw".into(),
+            None,
+            None,
+            None,
+        ));
+        vault
+            .data
+            .notes
+            .push(Note::new("codes".into(), "123".into()));
+
+        assert!(vault.verify_integrity().is_ok());
+    }
+
+    #[test]
+    fn test_reports_duplicates() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        let a = ApiKey::new("stripe".into(), "sk_1".into(), None, None);
+        let b = ApiKey::new("stripe".into(), "sk_2".into(), None, None);
+        let mut c = ApiKey::new("other".into(), "sk_3".into(), None, None);
+        c.id = a.id.clone();
+        let (a_id, b_id) = (a.id.clone(), b.id.clone());
+        vault.data.api_keys = vec![a, b, c];
+
+        assert_eq!(
+            kinds(&vault),
+            vec![
+                (b_id, IntegrityIssueKind::DuplicateName),
+                (a_id, IntegrityIssueKind::DuplicateId),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_empty_fields() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        let cred = DbCredential::new(
+            "prod".into(),
+            String::new(),
+            None,
+            "app".into(),
+            "admin".into(),
+            "pw".into(),
+            None,
+            None,
+        );
+        let token = Token::new("ci".into(), String::new(), None, None, None);
+        let (cred_id, token_id) = (cred.id.clone(), token.id.clone());
+        vault.data.db_credentials.push(cred);
+        vault.data.tokens.push(token);
+
+        assert_eq!(
+            kinds(&vault),
+            vec![
+                (cred_id, IntegrityIssueKind::EmptyField("host")),
+                (token_id, IntegrityIssueKind::EmptyField("token")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_malformed_id_and_timestamps() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        let mut bad_id = Note::new("a".into(), "x".into());
+        bad_id.id = "not-a-uuid".into();
+        let mut backwards = Note::new("b".into(), "y".into());
+        backwards.updated_at = backwards.created_at - Duration::seconds(1);
+        let backwards_id = backwards.id.clone();
+        vault.data.notes = vec![bad_id, backwards];
+
+        assert_eq!(
+            kinds(&vault),
+            vec![
+                ("not-a-uuid".to_string(), IntegrityIssueKind::MalformedId),
+                (backwards_id, IntegrityIssueKind::UpdatedBeforeCreated),
+            ]
+        );
+
+        let report = vault.verify_integrity();
+        assert_eq!(
+            report.issues[0].to_string(),
+            "note 'a' has a malformed id: not-a-uuid"
+        );
+    }
+}
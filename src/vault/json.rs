@@ -0,0 +1,309 @@
+//! JSON views of single secrets for scripting
+//!
+//! Each view carries the secret's metadata. Secret values are only included
+//! with `reveal`; otherwise they are replaced with `[REDACTED]` so the shape
+//! of the output stays the same either way.
+
+use super::secret::{SecretValue, REDACTED};
+use super::types::*;
+use super::{Vault, VaultError};
+use serde_json::{json, Map, Value};
+
+fn secret(value: &SecretValue, reveal: bool) -> Value {
+    if reveal {
+        Value::from(value.expose_secret())
+    } else {
+        Value::from(REDACTED)
+    }
+}
+
+fn custom_fields(fields: &[CustomField], reveal: bool) -> Value {
+    fields
+        .iter()
+        .map(|f| {
+            json!({
+                "name": f.name,
+                "value": secret(&f.value, reveal || !f.hidden),
+                "hidden": f.hidden,
+            })
+        })
+        .collect()
+}
+
+/// Fields every secret has, followed by the type-specific ones in `fields`
+fn with_metadata(entry: &dyn SecretEntry, fields: Value) -> Value {
+    let mut map = Map::new();
+    map.insert("type".into(), json!(entry.secret_type()));
+    map.insert("id".into(), json!(entry.id()));
+    map.insert("name".into(), json!(entry.name()));
+    if let Value::Object(fields) = fields {
+        map.extend(fields);
+    }
+    map.insert("tags".into(), json!(entry.tags()));
+    map.insert("created_at".into(), json!(entry.created_at()));
+    map.insert("updated_at".into(), json!(entry.updated_at()));
+    map.insert("last_accessed".into(), json!(entry.last_accessed()));
+    map.insert("access_count".into(), json!(entry.access_count()));
+    Value::Object(map)
+}
+
+impl Password {
+    /// Metadata plus, with `reveal`, the password
+    pub fn to_public_json(&self, reveal: bool) -> Value {
+        with_metadata(
+            self,
+            json!({
+                "description": self.description,
+                "username": self.username,
+                "url": self.url,
+                "password": secret(&self.password, reveal),
+                "custom_fields": custom_fields(&self.custom_fields, reveal),
+            }),
+        )
+    }
+}
+
+impl ApiKey {
+    /// Metadata plus, with `reveal`, the key
+    pub fn to_public_json(&self, reveal: bool) -> Value {
+        with_metadata(
+            self,
+            json!({
+                "description": self.description,
+                "service": self.service,
+                "key": secret(&self.key, reveal),
+                "custom_fields": custom_fields(&self.custom_fields, reveal),
+            }),
+        )
+    }
+}
+
+impl Note {
+    /// Metadata and attachment names plus, with `reveal`, the content
+    pub fn to_public_json(&self, reveal: bool) -> Value {
+        let attachments: Vec<Value> = self
+            .attachments
+            .iter()
+            .map(|a| json!({ "filename": a.filename, "mime_type": a.mime_type, "size": a.size }))
+            .collect();
+        with_metadata(
+            self,
+            json!({
+                "description": self.description,
+                "content": secret(&self.content, reveal),
+                "attachments": attachments,
+            }),
+        )
+    }
+}
+
+impl DbCredential {
+    /// Connection details plus, with `reveal`, the password
+    pub fn to_public_json(&self, reveal: bool) -> Value {
+        with_metadata(
+            self,
+            json!({
+                "description": self.description,
+                "db_type": self.db_type,
+                "host": self.host,
+                "port": self.port,
+                "database": self.database,
+                "username": self.username,
+                "password": secret(&self.password, reveal),
+            }),
+        )
+    }
+}
+
+impl Token {
+    /// Metadata plus, with `reveal`, the token
+    pub fn to_public_json(&self, reveal: bool) -> Value {
+        with_metadata(
+            self,
+            json!({
+                "description": self.description,
+                "token_type": self.token_type,
+                "expires_at": self.expires_at,
+                "token": secret(&self.token, reveal),
+                "custom_fields": custom_fields(&self.custom_fields, reveal),
+            }),
+        )
+    }
+}
+
+impl Totp {
+    /// Code parameters plus, with `reveal`, the seed
+    pub fn to_public_json(&self, reveal: bool) -> Value {
+        with_metadata(
+            self,
+            json!({
+                "issuer": self.issuer,
+                "algorithm": self.algorithm,
+                "digits": self.digits,
+                "period": self.period,
+                "secret": secret(&self.secret, reveal),
+            }),
+        )
+    }
+}
+
+impl SshKey {
+    /// Public key plus, with `reveal`, the private key and passphrase
+    pub fn to_public_json(&self, reveal: bool) -> Value {
+        with_metadata(
+            self,
+            json!({
+                "description": self.description,
+                "key_type": self.key_type,
+                "public_key": self.public_key,
+                "private_key": secret(&self.private_key, reveal),
+                "passphrase": self.passphrase.as_ref().map(|p| secret(p, reveal)),
+            }),
+        )
+    }
+}
+
+impl CreditCard {
+    /// Card details and last four digits plus, with `reveal`, number and CVV
+    pub fn to_public_json(&self, reveal: bool) -> Value {
+        with_metadata(
+            self,
+            json!({
+                "cardholder": self.cardholder,
+                "brand": self.brand,
+                "last_four": self.last_four(),
+                "expiry_month": self.expiry_month,
+                "expiry_year": self.expiry_year,
+                "number": secret(&self.number, reveal),
+                "cvv": secret(&self.cvv, reveal),
+            }),
+        )
+    }
+}
+
+impl WifiCredential {
+    /// Network details plus, with `reveal`, the password
+    pub fn to_public_json(&self, reveal: bool) -> Value {
+        with_metadata(
+            self,
+            json!({
+                "ssid": self.ssid,
+                "security": self.security,
+                "hidden": self.hidden,
+                "password": secret(&self.password, reveal),
+            }),
+        )
+    }
+}
+
+impl Vault {
+    /// Looks up a secret by id or name and returns its JSON view
+    pub fn get_as_json(
+        &self,
+        secret_type: SecretType,
+        id_or_name: &str,
+        reveal: bool,
+    ) -> Result<Value, VaultError> {
+        let json = match secret_type {
+            SecretType::Password => self
+                .get_password(id_or_name)
+                .map(|s| s.to_public_json(reveal)),
+            SecretType::ApiKey => self
+                .get_api_key(id_or_name)
+                .map(|s| s.to_public_json(reveal)),
+            SecretType::Note => self.get_note(id_or_name).map(|s| s.to_public_json(reveal)),
+            SecretType::DbCredential => self
+                .get_db_credential(id_or_name)
+                .map(|s| s.to_public_json(reveal)),
+            SecretType::Token => self.get_token(id_or_name).map(|s| s.to_public_json(reveal)),
+            SecretType::Totp => self.get_totp(id_or_name).map(|s| s.to_public_json(reveal)),
+            SecretType::SshKey => self
+                .get_ssh_key(id_or_name)
+                .map(|s| s.to_public_json(reveal)),
+            SecretType::CreditCard => self
+                .get_credit_card(id_or_name)
+                .map(|s| s.to_public_json(reveal)),
+            SecretType::WifiCredential => self
+                .get_wifi_credential(id_or_name)
+                .map(|s| s.to_public_json(reveal)),
+        };
+        json.ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+
+    fn test_vault() -> (tempfile::TempDir, Vault) {
+        let (dir, mut vault) = test_util::test_vault();
+        let mut password = Password::new(
+            "github".into(),
+            "hunter2".into(),
+            None,
+            Some("octocat".into()),
+            None,
+        );
+        password.custom_fields.push(CustomField {
+            name: "pin".into(),
+            value: "1234".into(),
+            hidden: true,
+        });
+        vault.add_password(password).unwrap();
+        (dir, vault)
+    }
+
+    #[test]
+    fn test_masked_json() {
+        let (_dir, vault) = test_vault();
+        let json = vault
+            .get_as_json(SecretType::Password, "github", false)
+            .unwrap();
+
+        assert_eq!(json["type"], "password");
+        assert_eq!(json["name"], "github");
+        assert_eq!(json["username"], "octocat");
+        assert_eq!(json["password"], REDACTED);
+        assert_eq!(json["custom_fields"][0]["value"], REDACTED);
+        assert!(!json.to_string().contains("hunter2"));
+        assert!(!json.to_string().contains("1234"));
+    }
+
+    #[test]
+    fn test_revealed_json() {
+        let (_dir, vault) = test_vault();
+        let json = vault
+            .get_as_json(SecretType::Password, "github", true)
+            .unwrap();
+
+        assert_eq!(json["password"], "hunter2");
+        assert_eq!(json["custom_fields"][0]["value"], "1234");
+    }
+
+    #[test]
+    fn test_card_json_keeps_last_four_when_masked() {
+        let card = CreditCard::new(
+            "visa".into(),
+            "Jane Doe".into(),
+            "4111111111111111".into(),
+            12,
+            2030,
+            "123".into(),
+            None,
+        );
+        let json = card.to_public_json(false);
+        assert_eq!(json["last_four"], "1111");
+        assert_eq!(json["number"], REDACTED);
+        assert_eq!(json["cvv"], REDACTED);
+    }
+
+    #[test]
+    fn test_missing_secret() {
+        let (_dir, vault) = test_vault();
+        assert!(matches!(
+            vault.get_as_json(SecretType::Note, "github", false),
+            Err(VaultError::SecretNotFound(_))
+        ));
+    }
+}
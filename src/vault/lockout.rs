@@ -0,0 +1,293 @@
+//! Opt-in lockout after repeated wrong master passwords
+//!
+//! With a `LockoutPolicy` set, every wrong password given to `Vault::unlock`
+//! or `ReadOnlyVault::open` is counted in the vault file's unencrypted
+//! envelope. Reaching the limit either refuses all attempts for a cooldown
+//! or, with `wipe` set, destroys the vault file. A correct password given to
+//! `Vault::unlock` resets the count.
+//!
+//! The counter lives next to the ciphertext, so this slows down guessing
+//! through kookie itself; someone who can edit the file can also reset it.
+
+use super::{storage, VaultError, VaultFile};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Cooldown used when none is given
+pub const DEFAULT_LOCKOUT_MINUTES: u32 = 15;
+
+/// When to stop accepting master passwords
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockoutPolicy {
+    /// Consecutive wrong passwords that trigger the lockout
+    pub max_attempts: u32,
+    /// How long unlocking is refused afterwards
+    pub cooldown_minutes: u32,
+    /// Overwrite and delete the vault file instead of cooling down
+    #[serde(default)]
+    pub wipe: bool,
+}
+
+impl LockoutPolicy {
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            cooldown_minutes: DEFAULT_LOCKOUT_MINUTES,
+            wipe: false,
+        }
+    }
+}
+
+/// Fails with `LockedOut` while the file's cooldown hasn't passed
+pub(super) fn check(file: &VaultFile, now: DateTime<Utc>) -> Result<(), VaultError> {
+    match file.lockout_until {
+        Some(until) if until > now => Err(VaultError::LockedOut { until }),
+        _ => Ok(()),
+    }
+}
+
+/// Counts a wrong password against `file` and returns the error to report
+///
+/// Without a policy nothing is recorded and the error is `WrongPassword`.
+/// A stored policy allowing 0 attempts (which `set_lockout_policy` refuses)
+/// counts as no policy.
+pub(super) fn record_failure(path: &Path, mut file: VaultFile, now: DateTime<Utc>) -> VaultError {
+    let Some(policy) = file.lockout_policy.filter(|p| p.max_attempts > 0) else {
+        return VaultError::WrongPassword;
+    };
+
+    file.failed_attempts += 1;
+    if file.failed_attempts < policy.max_attempts {
+        return match storage::save_vault_file(path, &file) {
+            Ok(()) => VaultError::WrongPassword,
+            Err(e) => e,
+        };
+    }
+
+    if policy.wipe {
+        return match wipe(path) {
+            Ok(()) => VaultError::Wiped,
+            Err(e) => e.into(),
+        };
+    }
+
+    let until = now + Duration::minutes(i64::from(policy.cooldown_minutes));
+    file.failed_attempts = 0;
+    file.lockout_until = Some(until);
+    match storage::save_vault_file(path, &file) {
+        Ok(()) => VaultError::LockedOut { until },
+        Err(e) => e,
+    }
+}
+
+/// Clears the failure count after a correct password, if there is one to clear
+pub(super) fn record_success(path: &Path, mut file: VaultFile) -> Result<(), VaultError> {
+    if file.failed_attempts == 0 && file.lockout_until.is_none() {
+        return Ok(());
+    }
+    file.failed_attempts = 0;
+    file.lockout_until = None;
+    storage::save_vault_file(path, &file)
+}
+
+/// Overwrites the file with zeros before deleting it
+fn wipe(path: &Path) -> std::io::Result<()> {
+    let len = fs::metadata(path)?.len() as usize;
+    fs::write(path, vec![0u8; len])?;
+    fs::remove_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use crate::vault::types::Note;
+    use crate::vault::Vault;
+    use tempfile::TempDir;
+
+    fn test_vault(policy: LockoutPolicy) -> (TempDir, Vault) {
+        let (dir, mut vault) = test_util::test_vault();
+        vault
+            .add_note(Note::new("codes".into(), "111".into()))
+            .unwrap();
+        vault.set_lockout_policy(Some(policy)).unwrap();
+        (dir, vault)
+    }
+
+    fn load(vault: &Vault) -> VaultFile {
+        storage::load_vault_file(&vault.path).unwrap()
+    }
+
+    #[test]
+    fn test_threshold_locks_out_even_the_right_password() {
+        let (_dir, vault) = test_vault(LockoutPolicy::new(3));
+        let mut other = Vault::with_path(vault.path.clone());
+
+        for attempt in 1..=2 {
+            assert!(matches!(
+                other.unlock("wrong"),
+                Err(VaultError::WrongPassword)
+            ));
+            assert_eq!(load(&vault).failed_attempts, attempt);
+        }
+        let locked = other.unlock("wrong");
+        assert!(matches!(locked, Err(VaultError::LockedOut { until }) if until > Utc::now()));
+
+        assert!(matches!(
+            other.unlock("pw"),
+            Err(VaultError::LockedOut { .. })
+        ));
+        assert!(!other.is_unlocked());
+    }
+
+    #[test]
+    fn test_success_clears_the_counter() {
+        let (_dir, vault) = test_vault(LockoutPolicy::new(3));
+        let mut other = Vault::with_path(vault.path.clone());
+
+        other.unlock("wrong").unwrap_err();
+        other.unlock("wrong").unwrap_err();
+        other.unlock("pw").unwrap();
+        assert_eq!(load(&vault).failed_attempts, 0);
+
+        // The count starts over, so two more mistakes don't lock
+        let mut third = Vault::with_path(vault.path.clone());
+        third.unlock("wrong").unwrap_err();
+        assert!(matches!(
+            third.unlock("wrong"),
+            Err(VaultError::WrongPassword)
+        ));
+    }
+
+    #[test]
+    fn test_expired_lockout_allows_unlock() {
+        let (_dir, vault) = test_vault(LockoutPolicy::new(1));
+        let mut other = Vault::with_path(vault.path.clone());
+        assert!(matches!(
+            other.unlock("wrong"),
+            Err(VaultError::LockedOut { .. })
+        ));
+
+        let mut file = load(&vault);
+        file.lockout_until = Some(Utc::now() - Duration::minutes(1));
+        storage::save_vault_file(&vault.path, &file).unwrap();
+
+        other.unlock("pw").unwrap();
+        assert!(other.get_note("codes").is_some());
+        assert_eq!(load(&vault).lockout_until, None);
+    }
+
+    #[test]
+    fn test_policy_survives_saves() {
+        let (_dir, mut vault) = test_vault(LockoutPolicy::new(5));
+        vault
+            .add_note(Note::new("more".into(), "222".into()))
+            .unwrap();
+        assert_eq!(load(&vault).lockout_policy, Some(LockoutPolicy::new(5)));
+
+        let mut session = Vault::with_path(vault.path.clone());
+        session.unlock("pw").unwrap();
+        assert_eq!(session.lockout_policy(), Some(LockoutPolicy::new(5)));
+    }
+
+    #[test]
+    fn test_wipe_destroys_the_vault() {
+        let policy = LockoutPolicy {
+            wipe: true,
+            ..LockoutPolicy::new(2)
+        };
+        let (_dir, vault) = test_vault(policy);
+        let mut other = Vault::with_path(vault.path.clone());
+
+        assert!(matches!(
+            other.unlock("wrong"),
+            Err(VaultError::WrongPassword)
+        ));
+        assert!(matches!(other.unlock("wrong"), Err(VaultError::Wiped)));
+        assert!(!vault.path.exists());
+    }
+
+    #[test]
+    fn test_no_policy_records_nothing() {
+        let (_dir, vault) = test_util::test_vault();
+        let before = fs::read(&vault.path).unwrap();
+
+        let mut other = Vault::with_path(vault.path.clone());
+        for _ in 0..5 {
+            assert!(matches!(
+                other.unlock("wrong"),
+                Err(VaultError::WrongPassword)
+            ));
+        }
+        assert_eq!(fs::read(&vault.path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_zero_attempts_is_rejected() {
+        let (_dir, mut vault) = test_util::test_vault();
+        let policy = LockoutPolicy {
+            wipe: true,
+            ..LockoutPolicy::new(0)
+        };
+        assert!(matches!(
+            vault.set_lockout_policy(Some(policy)),
+            Err(VaultError::InvalidLockoutPolicy)
+        ));
+        assert_eq!(vault.lockout_policy(), None);
+
+        // Even if a file carries one, a wrong password doesn't wipe it
+        let mut file = load(&vault);
+        file.lockout_policy = Some(policy);
+        storage::save_vault_file(&vault.path, &file).unwrap();
+        let mut other = Vault::with_path(vault.path.clone());
+        assert!(matches!(
+            other.unlock("wrong"),
+            Err(VaultError::WrongPassword)
+        ));
+        assert!(vault.path.exists());
+        assert_eq!(load(&vault).failed_attempts, 0);
+    }
+
+    #[test]
+    fn test_save_keeps_failures_from_other_processes() {
+        let (_dir, mut vault) = test_vault(LockoutPolicy::new(3));
+        let mut other = Vault::with_path(vault.path.clone());
+        other.unlock("wrong").unwrap_err();
+
+        vault
+            .add_note(Note::new("more".into(), "222".into()))
+            .unwrap();
+        assert_eq!(load(&vault).failed_attempts, 1);
+
+        other.unlock("wrong").unwrap_err();
+        assert!(matches!(
+            other.unlock("wrong"),
+            Err(VaultError::LockedOut { .. })
+        ));
+        let until = load(&vault).lockout_until;
+        vault.save().unwrap();
+        assert_eq!(load(&vault).lockout_until, until);
+    }
+
+    #[test]
+    fn test_change_password_counts_failures_under_the_lock() {
+        let (_dir, mut vault) = test_vault(LockoutPolicy::new(3));
+        {
+            let _held = storage::VaultLock::acquire(&vault.path).unwrap();
+            assert!(matches!(
+                vault.change_master_password("wrong", "new"),
+                Err(VaultError::Locked(_))
+            ));
+        }
+        assert_eq!(load(&vault).failed_attempts, 0);
+
+        assert!(matches!(
+            vault.change_master_password("wrong", "new"),
+            Err(VaultError::WrongPassword)
+        ));
+        assert_eq!(load(&vault).failed_attempts, 1);
+        vault.change_master_password("pw", "new").unwrap();
+    }
+}
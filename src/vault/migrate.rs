@@ -0,0 +1,129 @@
+//! Vault file format migrations
+//!
+//! Older vault files are upgraded in memory one version at a time until they
+//! reach `VAULT_VERSION`. Each step only fills in what the newer format makes
+//! explicit; the encrypted payload is untouched, so the next save writes the
+//! current format.
+
+use super::{VaultError, VaultFile, VAULT_VERSION};
+use crate::crypto::kdf::KdfParams;
+use crate::crypto::Algorithm;
+
+/// Upgrades `file` to the current format version
+pub fn migrate(mut file: VaultFile) -> Result<VaultFile, VaultError> {
+    if file.version > VAULT_VERSION {
+        return Err(VaultError::UnsupportedVersion {
+            found: file.version,
+            max_supported: VAULT_VERSION,
+        });
+    }
+
+    while file.version < VAULT_VERSION {
+        file = match file.version {
+            0 | 1 => v1_to_v2(file),
+            2 => v2_to_v3(file),
+            3 => v3_to_v4(file),
+            4 => v4_to_v5(file),
+            v => {
+                return Err(VaultError::UnsupportedVersion {
+                    found: v,
+                    max_supported: VAULT_VERSION,
+                })
+            }
+        };
+    }
+
+    Ok(file)
+}
+
+/// v1 used fixed Argon2id parameters; v2 stores them in the file
+fn v1_to_v2(mut file: VaultFile) -> VaultFile {
+    file.kdf_params = Some(KdfParams::LEGACY);
+    file.version = 2;
+    file
+}
+
+/// v2 was always AES-256-GCM; v3 stores the cipher in the file
+fn v2_to_v3(mut file: VaultFile) -> VaultFile {
+    file.kdf_params.get_or_insert(KdfParams::LEGACY);
+    file.algorithm = Some(Algorithm::Aes256Gcm);
+    file.version = 3;
+    file
+}
+
+/// v4 only adds optional wrapped keys; a v3 file simply has none
+fn v3_to_v4(mut file: VaultFile) -> VaultFile {
+    file.version = 4;
+    file
+}
+
+/// v5 normalizes the master password; older files were keyed with it as typed
+fn v4_to_v5(mut file: VaultFile) -> VaultFile {
+    file.raw_password = true;
+    file.version = 5;
+    file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1_FIXTURE: &str = r#"{
+        "version": 1,
+        "salt": "c2FsdHNhbHRzYWx0c2FsdA",
+        "encrypted_data": "AAAA",
+        "created_at": "2024-01-01T00:00:00Z",
+        "modified_at": "2024-01-02T00:00:00Z"
+    }"#;
+
+    #[test]
+    fn test_v1_fixture_migrates_to_current() {
+        let file: VaultFile = serde_json::from_str(V1_FIXTURE).unwrap();
+        let migrated = migrate(file).unwrap();
+
+        assert_eq!(migrated.version, VAULT_VERSION);
+        assert_eq!(migrated.kdf_params, Some(KdfParams::LEGACY));
+        assert_eq!(migrated.algorithm, Some(Algorithm::Aes256Gcm));
+        assert!(!migrated.chunked);
+        assert_eq!(migrated.encrypted_data, "AAAA");
+    }
+
+    #[test]
+    fn test_pre_v5_files_are_marked_raw_password() {
+        let mut file: VaultFile = serde_json::from_str(V1_FIXTURE).unwrap();
+        file.version = 4;
+        assert!(migrate(file).unwrap().raw_password);
+
+        let mut current: VaultFile = serde_json::from_str(V1_FIXTURE).unwrap();
+        current.version = VAULT_VERSION;
+        assert!(!migrate(current).unwrap().raw_password);
+    }
+
+    #[test]
+    fn test_v2_keeps_stored_kdf_params() {
+        let mut file: VaultFile = serde_json::from_str(V1_FIXTURE).unwrap();
+        let params = KdfParams {
+            memory_cost: 8192,
+            iterations: 1,
+            parallelism: 1,
+        };
+        file.version = 2;
+        file.kdf_params = Some(params);
+
+        let migrated = migrate(file).unwrap();
+        assert_eq!(migrated.version, VAULT_VERSION);
+        assert_eq!(migrated.kdf_params(), params);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let mut file: VaultFile = serde_json::from_str(V1_FIXTURE).unwrap();
+        file.version = VAULT_VERSION + 1;
+
+        assert!(matches!(
+            migrate(file),
+            Err(VaultError::UnsupportedVersion { found, max_supported })
+                if found == VAULT_VERSION + 1 && max_supported == VAULT_VERSION
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+//! Password rules enforced when adding or changing passwords
+//!
+//! The policy is stored inside the encrypted vault data, so it can't be
+//! loosened without the master password. Imports are not checked; the
+//! policy only applies to passwords entered through `add_password` and
+//! `update_password`.
+
+use super::VaultError;
+use crate::utils::strength;
+use serde::{Deserialize, Serialize};
+
+/// Requirements every new password value must meet
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    /// Minimum length in characters
+    #[serde(default)]
+    pub min_length: usize,
+    #[serde(default)]
+    pub require_symbol: bool,
+    #[serde(default)]
+    pub require_digit: bool,
+    /// Both lower- and uppercase letters
+    #[serde(default)]
+    pub require_mixed_case: bool,
+    /// Reject well-known passwords like `Password123!`
+    #[serde(default)]
+    pub forbid_common: bool,
+}
+
+impl PasswordPolicy {
+    /// Describes each rule `password` breaks, in a fixed order
+    pub fn violations(&self, password: &str) -> Vec<String> {
+        let mut failed = Vec::new();
+
+        let length = password.chars().count();
+        if length < self.min_length {
+            failed.push(format!(
+                "must be at least {} characters (is {})",
+                self.min_length, length
+            ));
+        }
+        if self.require_symbol && password.chars().all(|c| c.is_ascii_alphanumeric()) {
+            failed.push("must contain a symbol".to_string());
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            failed.push("must contain a digit".to_string());
+        }
+        if self.require_mixed_case
+            && !(password.chars().any(|c| c.is_lowercase())
+                && password.chars().any(|c| c.is_uppercase()))
+        {
+            failed.push("must mix upper- and lowercase letters".to_string());
+        }
+        if self.forbid_common && strength::is_common(password) {
+            failed.push("is a commonly used password".to_string());
+        }
+
+        failed
+    }
+
+    /// Fails with `PolicyViolation` listing every broken rule
+    pub fn check(&self, password: &str) -> Result<(), VaultError> {
+        let failed = self.violations(password);
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(VaultError::PolicyViolation(failed))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 12,
+            require_symbol: true,
+            require_digit: true,
+            require_mixed_case: true,
+            forbid_common: true,
+        }
+    }
+
+    #[test]
+    fn test_compliant_password() {
+        assert!(strict().violations("Tr0ub4dor&3-horse").is_empty());
+        assert!(strict().check("Tr0ub4dor&3-horse").is_ok());
+    }
+
+    #[test]
+    fn test_each_rule_reported() {
+        let failed = strict().violations("password");
+        assert_eq!(failed.len(), 5);
+        assert!(failed[0].contains("at least 12"));
+        assert!(failed[1].contains("symbol"));
+        assert!(failed[2].contains("digit"));
+        assert!(failed[3].contains("upper- and lowercase"));
+        assert!(failed[4].contains("commonly used"));
+    }
+
+    #[test]
+    fn test_common_with_decoration_rejected() {
+        let policy = PasswordPolicy {
+            forbid_common: true,
+            ..Default::default()
+        };
+        assert!(!policy.violations("Password123!").is_empty());
+        assert!(policy.violations("testing-my-own-phrase").is_empty());
+    }
+
+    #[test]
+    fn test_default_policy_allows_anything() {
+        assert!(PasswordPolicy::default().violations("a").is_empty());
+    }
+}
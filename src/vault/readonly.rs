@@ -0,0 +1,222 @@
+//! Read-only access to an unlocked vault
+//!
+//! `ReadOnlyVault` wraps a `Vault` but only exposes lookups, so audits and
+//! other inspection can't save the file by accident. There is no way to get
+//! the inner `Vault` back out.
+//!
+//! A successful open doesn't write either: no lock file is created and an
+//! earlier failure count is left as it is. Wrong passwords are still counted
+//! against the lockout policy, just like with `Vault::unlock`.
+
+use super::doctor::DoctorReport;
+use super::integrity::IntegrityReport;
+use super::search::{SearchHit, SearchOptions};
+use super::types::*;
+use super::{lockout, storage, Vault, VaultData, VaultError};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+
+/// An unlocked vault that can be inspected but never saved
+pub struct ReadOnlyVault {
+    vault: Vault,
+}
+
+/// Forwards `&self` methods to the wrapped vault
+macro_rules! delegate {
+    ($($(#[$meta:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        $(
+            $(#[$meta])*
+            pub fn $name(&self $(, $arg: $ty)*) -> $ret {
+                self.vault.$name($($arg),*)
+            }
+        )*
+    };
+}
+
+impl Vault {
+    /// Unlocks the default vault for read-only use
+    pub fn open_readonly(master_password: &str) -> Result<ReadOnlyVault, VaultError> {
+        ReadOnlyVault::open(storage::get_vault_path()?, master_password)
+    }
+}
+
+impl Vault {
+    /// Decrypts the vault file into memory, writing only to count a wrong password
+    fn unlock_without_writing(&mut self, master_password: &str) -> Result<(), VaultError> {
+        if !self.exists() {
+            return Err(VaultError::NotInitialized);
+        }
+        let vault_file = self.load_file()?;
+        vault_file.check_salt()?;
+        lockout::check(&vault_file, Utc::now())?;
+        self.adopt_file(&vault_file);
+
+        let (key, decrypted) = match vault_file.open(master_password) {
+            Ok(opened) => opened,
+            Err(VaultError::WrongPassword) => {
+                // Count the failure on a fresh copy under the lock so it
+                // can't overwrite another process's save
+                let _lock = storage::VaultLock::acquire(&self.path)?;
+                let vault_file = self.load_file()?;
+                lockout::check(&vault_file, Utc::now())?;
+                return Err(lockout::record_failure(&self.path, vault_file, Utc::now()));
+            }
+            Err(e) => return Err(e),
+        };
+        self.data = serde_json::from_slice(&decrypted)?;
+        self.key = Some(key);
+        Ok(())
+    }
+}
+
+impl ReadOnlyVault {
+    /// Unlocks the vault at `path` for read-only use
+    pub fn open(path: PathBuf, master_password: &str) -> Result<Self, VaultError> {
+        let mut vault = Vault::with_path(path);
+        vault.unlock_without_writing(master_password)?;
+        Ok(Self { vault })
+    }
+
+    /// Returns the path of the underlying vault file
+    pub fn path(&self) -> &Path {
+        &self.vault.path
+    }
+
+    /// Returns the decrypted vault contents
+    pub fn data(&self) -> &VaultData {
+        &self.vault.data
+    }
+
+    delegate! {
+        fn get_password(&self, id_or_name: &str) -> Option<&Password>;
+        fn get_api_key(&self, id_or_name: &str) -> Option<&ApiKey>;
+        fn get_note(&self, id_or_name: &str) -> Option<&Note>;
+        fn get_db_credential(&self, id_or_name: &str) -> Option<&DbCredential>;
+        fn get_token(&self, id_or_name: &str) -> Option<&Token>;
+        fn get_totp(&self, id_or_name: &str) -> Option<&Totp>;
+        fn get_ssh_key(&self, id_or_name: &str) -> Option<&SshKey>;
+        fn get_credit_card(&self, id_or_name: &str) -> Option<&CreditCard>;
+        fn get_wifi_credential(&self, id_or_name: &str) -> Option<&WifiCredential>;
+
+        fn list_passwords(&self) -> &[Password];
+        fn list_passwords_sorted(&self, by: SortKey, desc: bool) -> Vec<&Password>;
+        fn list_api_keys(&self) -> &[ApiKey];
+        fn list_notes(&self) -> &[Note];
+        fn list_db_credentials(&self) -> &[DbCredential];
+        fn list_tokens(&self) -> &[Token];
+        fn list_totps(&self) -> &[Totp];
+        fn list_ssh_keys(&self) -> &[SshKey];
+        fn list_credit_cards(&self) -> &[CreditCard];
+        fn list_wifi_credentials(&self) -> &[WifiCredential];
+        fn list_all(&self) -> Vec<(SecretType, String, String)>;
+        fn find_by_tag(&self, tag: &str) -> Vec<(SecretType, String)>;
+
+        fn search(&self, query: &str) -> Vec<SearchHit>;
+        fn search_with(&self, options: &SearchOptions) -> Vec<SearchHit>;
+        fn password_history(&self, name: &str) -> Option<&[PasswordHistoryEntry]>;
+        fn expired_tokens(&self) -> Vec<&Token>;
+        fn reused_passwords(&self) -> Vec<(String, Vec<String>)>;
+        fn verify_integrity(&self) -> IntegrityReport;
+        fn doctor(&self) -> DoctorReport;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use std::fs;
+
+    #[test]
+    fn test_reads_never_touch_the_file() {
+        let (_dir, mut vault) = test_util::test_vault();
+        let path = vault.path.clone();
+        vault
+            .add_password(Password::new(
+                "github".into(),
+                "hunter2".into(),
+                None,
+                None,
+                None,
+            ))
+            .unwrap();
+        drop(vault);
+
+        let bytes_before = fs::read(&path).unwrap();
+        let mtime_before = fs::metadata(&path).unwrap().modified().unwrap();
+
+        let readonly = ReadOnlyVault::open(path.clone(), test_util::PASSWORD).unwrap();
+        for _ in 0..100 {
+            assert!(readonly.get_password("github").is_some());
+            assert_eq!(readonly.list_all().len(), 1);
+            assert_eq!(readonly.search("git").len(), 1);
+            readonly.doctor();
+        }
+        drop(readonly);
+
+        assert_eq!(fs::read(&path).unwrap(), bytes_before);
+        assert_eq!(
+            fs::metadata(&path).unwrap().modified().unwrap(),
+            mtime_before
+        );
+    }
+
+    #[test]
+    fn test_wrong_passwords_count_towards_lockout() {
+        let (_dir, mut vault) = test_util::test_vault();
+        let path = vault.path.clone();
+        vault
+            .set_lockout_policy(Some(lockout::LockoutPolicy::new(3)))
+            .unwrap();
+        drop(vault);
+
+        for attempt in 1..=2 {
+            assert!(matches!(
+                ReadOnlyVault::open(path.clone(), "nope"),
+                Err(VaultError::WrongPassword)
+            ));
+            assert_eq!(
+                storage::load_vault_file(&path).unwrap().failed_attempts,
+                attempt
+            );
+        }
+        assert!(matches!(
+            ReadOnlyVault::open(path.clone(), "nope"),
+            Err(VaultError::LockedOut { .. })
+        ));
+        assert!(matches!(
+            ReadOnlyVault::open(path, test_util::PASSWORD),
+            Err(VaultError::LockedOut { .. })
+        ));
+    }
+
+    #[test]
+    fn test_correct_open_writes_nothing() {
+        let (dir, mut vault) = test_util::test_vault();
+        let path = vault.path.clone();
+        vault
+            .set_lockout_policy(Some(lockout::LockoutPolicy::new(5)))
+            .unwrap();
+        drop(vault);
+        assert!(ReadOnlyVault::open(path.clone(), "nope").is_err());
+        fs::remove_file(dir.path().join("vault.json.lock")).unwrap();
+
+        let bytes_before = fs::read(&path).unwrap();
+        ReadOnlyVault::open(path.clone(), test_util::PASSWORD).unwrap();
+
+        // The earlier failure is left for `Vault::unlock` to clear
+        assert_eq!(fs::read(&path).unwrap(), bytes_before);
+        assert_eq!(storage::load_vault_file(&path).unwrap().failed_attempts, 1);
+        assert!(!dir.path().join("vault.json.lock").exists());
+    }
+
+    #[test]
+    fn test_wrong_password_is_rejected() {
+        let (_dir, vault) = test_util::test_vault();
+
+        assert!(matches!(
+            ReadOnlyVault::open(vault.path.clone(), "nope"),
+            Err(VaultError::WrongPassword)
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+//! Recovery key as a second way to unlock the vault
+//!
+//! Without a recovery key the vault contents are encrypted directly with the
+//! key derived from the master password. Generating one switches the vault to
+//! envelope encryption: the contents are encrypted with a random data key,
+//! and the file stores that data key twice, wrapped once under the master
+//! password key and once under a key derived from the recovery key. Either
+//! one unlocks the vault, and changing the master password only re-wraps the
+//! data key.
+//!
+//! Wrapped keys always use AES-256-GCM so switching the vault cipher doesn't
+//! orphan them.
+
+use super::{storage, AuditEvent, Vault, VaultError, VaultFile};
+use crate::crypto::{self, kdf, Algorithm};
+use data_encoding::BASE32_NOPAD;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Random bytes in a recovery key (160 bits, 32 base32 characters)
+const RECOVERY_KEY_BYTES: usize = 20;
+
+/// Characters per dash-separated group when displaying a recovery key
+const GROUP_LEN: usize = 4;
+
+/// The data key wrapped under a recovery key
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecoveryWrap {
+    /// Salt for deriving the wrapping key from the recovery key
+    pub salt: String,
+    pub wrapped_key: String,
+}
+
+/// Encrypts `data_key` under `wrapping_key`
+pub(super) fn wrap_key(wrapping_key: &[u8; 32], data_key: &[u8; 32]) -> Result<String, VaultError> {
+    crypto::encrypt_with(Algorithm::Aes256Gcm, wrapping_key, data_key)
+        .map_err(|e| VaultError::EncryptionError(e.to_string()))
+}
+
+/// Decrypts a key wrapped by `wrap_key`; any failure means the wrong key
+pub(super) fn unwrap_key(
+    wrapping_key: &[u8; 32],
+    wrapped: &str,
+) -> Result<Zeroizing<[u8; 32]>, VaultError> {
+    let bytes = Zeroizing::new(
+        crypto::decrypt_with(Algorithm::Aes256Gcm, wrapping_key, wrapped)
+            .map_err(|_| VaultError::WrongPassword)?,
+    );
+    let key: [u8; 32] = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| VaultError::WrongPassword)?;
+    Ok(Zeroizing::new(key))
+}
+
+fn random_key() -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    OsRng.fill_bytes(key.as_mut_slice());
+    key
+}
+
+/// Returns a new recovery key formatted as `ABCD-EFGH-...`
+fn new_recovery_key() -> String {
+    let mut bytes = Zeroizing::new([0u8; RECOVERY_KEY_BYTES]);
+    OsRng.fill_bytes(bytes.as_mut_slice());
+    let encoded = Zeroizing::new(BASE32_NOPAD.encode(bytes.as_slice()));
+    encoded
+        .as_bytes()
+        .chunks(GROUP_LEN)
+        .map(|group| String::from_utf8_lossy(group).into_owned())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Strips dashes and whitespace and uppercases, so typing is forgiving
+fn normalize(recovery_key: &str) -> Zeroizing<String> {
+    Zeroizing::new(
+        recovery_key
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .collect::<String>()
+            .to_uppercase(),
+    )
+}
+
+fn recovery_wrapping_key(
+    params: kdf::KdfParams,
+    recovery_key: &str,
+    salt: &str,
+) -> Result<Zeroizing<[u8; 32]>, VaultError> {
+    Ok(Zeroizing::new(kdf::derive_key_with_params(
+        &normalize(recovery_key),
+        salt,
+        params,
+    )?))
+}
+
+impl Vault {
+    /// Creates a recovery key for the unlocked vault and returns it
+    ///
+    /// The key is shown only once; it is not stored anywhere in readable
+    /// form. Generating a new one invalidates the previous recovery key. The
+    /// first call switches the vault to a random data key, so any cached
+    /// session key stops working.
+    pub fn generate_recovery_key(&mut self) -> Result<String, VaultError> {
+        let current = self.key.as_ref().ok_or(VaultError::WrongPassword)?;
+
+        let data_key = if self.wrapped_key.is_some() {
+            current.clone()
+        } else {
+            // The current key is the master password key; wrap a new data key under it
+            let data_key = random_key();
+            self.wrapped_key = Some(wrap_key(current, &data_key)?);
+            data_key
+        };
+
+        let recovery_key = new_recovery_key();
+        let salt = kdf::generate_salt();
+        let wrapping_key = recovery_wrapping_key(self.kdf_params, &recovery_key, &salt)?;
+        self.recovery = Some(RecoveryWrap {
+            wrapped_key: wrap_key(&wrapping_key, &data_key)?,
+            salt,
+        });
+        self.key = Some(data_key);
+
+        self.save()?;
+        Ok(recovery_key)
+    }
+
+    /// True if the vault can be unlocked with a recovery key
+    pub fn has_recovery_key(&self) -> bool {
+        self.recovery.is_some()
+    }
+
+    /// Unlocks the vault with its recovery key instead of the master password
+    ///
+    /// Dashes, spaces and case in `recovery_key` are ignored.
+    pub fn unlock_with_recovery_key(&mut self, recovery_key: &str) -> Result<(), VaultError> {
+        if !self.exists() {
+            return Err(VaultError::NotInitialized);
+        }
+
+        let _lock = storage::VaultLock::acquire(&self.path)?;
+        let vault_file = self.load_file()?;
+        let key = Self::recover_data_key(&vault_file, recovery_key)?;
+        let decrypted = vault_file
+            .decrypt(&key)
+            .map(Zeroizing::new)
+            .map_err(|_| VaultError::InvalidRecoveryKey)?;
+
+        self.data = serde_json::from_slice(&decrypted)?;
+        self.adopt_file(&vault_file);
+        self.key = Some(key);
+        self.audit(AuditEvent::Unlock, None);
+
+        Ok(())
+    }
+
+    /// Sets a new master password after verifying the recovery key
+    ///
+    /// For when the master password is forgotten. The recovery key stays
+    /// valid, and the wrong-password counter is reset.
+    pub fn reset_master_password(
+        &mut self,
+        recovery_key: &str,
+        new_password: &str,
+    ) -> Result<(), VaultError> {
+        self.unlock_with_recovery_key(recovery_key)?;
+
+        let data_key = self.key.as_ref().ok_or(VaultError::WrongPassword)?;
+        self.salt = kdf::generate_salt();
+        let master_key = Zeroizing::new(kdf::derive_key_with_params(
+            &kdf::normalize_password(new_password),
+            &self.salt,
+            self.kdf_params,
+        )?);
+        self.wrapped_key = Some(wrap_key(&master_key, data_key)?);
+        self.raw_password = false;
+
+        self.save()
+    }
+
+    fn recover_data_key(
+        vault_file: &VaultFile,
+        recovery_key: &str,
+    ) -> Result<Zeroizing<[u8; 32]>, VaultError> {
+        let wrap = vault_file
+            .recovery
+            .as_ref()
+            .ok_or(VaultError::NoRecoveryKey)?;
+        let wrapping_key =
+            recovery_wrapping_key(vault_file.kdf_params(), recovery_key, &wrap.salt)?;
+        unwrap_key(&wrapping_key, &wrap.wrapped_key).map_err(|_| VaultError::InvalidRecoveryKey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use crate::vault::types::Note;
+
+    fn test_vault() -> (tempfile::TempDir, Vault) {
+        let (dir, mut vault) = test_util::test_vault();
+        vault
+            .add_note(Note::new("codes".into(), "111".into()))
+            .unwrap();
+        (dir, vault)
+    }
+
+    #[test]
+    fn test_recovery_key_format() {
+        let key = new_recovery_key();
+        assert_eq!(key.len(), 32 + 7);
+        assert!(key.split('-').all(|group| group.len() == GROUP_LEN));
+        assert_eq!(
+            normalize(&key.to_lowercase()).as_str(),
+            key.replace('-', "")
+        );
+    }
+
+    #[test]
+    fn test_unlock_with_recovery_key_after_forgotten_password() {
+        let (_dir, mut vault) = test_vault();
+        let recovery_key = vault.generate_recovery_key().unwrap();
+        assert!(vault.has_recovery_key());
+
+        // Master password still works after switching to a data key
+        let mut by_password = Vault::with_path(vault.path.clone());
+        by_password.unlock("pw").unwrap();
+        assert!(by_password.get_note("codes").is_some());
+
+        let mut forgotten = Vault::with_path(vault.path.clone());
+        assert!(matches!(
+            forgotten.unlock("guess"),
+            Err(VaultError::WrongPassword)
+        ));
+        forgotten
+            .unlock_with_recovery_key(&recovery_key.to_lowercase().replace('-', " "))
+            .unwrap();
+        assert!(forgotten.get_note("codes").is_some());
+    }
+
+    #[test]
+    fn test_reset_master_password_keeps_recovery_key() {
+        let (_dir, mut vault) = test_vault();
+        let recovery_key = vault.generate_recovery_key().unwrap();
+
+        let mut reset = Vault::with_path(vault.path.clone());
+        reset
+            .reset_master_password(&recovery_key, "new-pw")
+            .unwrap();
+
+        let mut reopened = Vault::with_path(vault.path.clone());
+        assert!(reopened.unlock("pw").is_err());
+        reopened.unlock("new-pw").unwrap();
+        assert!(reopened.get_note("codes").is_some());
+
+        let mut again = Vault::with_path(vault.path.clone());
+        again.unlock_with_recovery_key(&recovery_key).unwrap();
+    }
+
+    #[test]
+    fn test_wrong_or_missing_recovery_key() {
+        let (_dir, mut vault) = test_vault();
+        let mut other = Vault::with_path(vault.path.clone());
+        assert!(matches!(
+            other.unlock_with_recovery_key("AAAA-BBBB"),
+            Err(VaultError::NoRecoveryKey)
+        ));
+
+        let recovery_key = vault.generate_recovery_key().unwrap();
+        let old = recovery_key.clone();
+        let new = vault.generate_recovery_key().unwrap();
+        assert_ne!(old, new);
+        assert!(matches!(
+            other.unlock_with_recovery_key(&old),
+            Err(VaultError::InvalidRecoveryKey)
+        ));
+        other.unlock_with_recovery_key(&new).unwrap();
+    }
+
+    #[test]
+    fn test_change_master_password_rewraps_data_key() {
+        let (_dir, mut vault) = test_vault();
+        let recovery_key = vault.generate_recovery_key().unwrap();
+
+        vault.change_master_password("pw", "pw2").unwrap();
+
+        let mut reopened = Vault::with_path(vault.path.clone());
+        reopened.unlock("pw2").unwrap();
+        let mut recovered = Vault::with_path(vault.path.clone());
+        recovered.unlock_with_recovery_key(&recovery_key).unwrap();
+        assert!(recovered.get_note("codes").is_some());
+    }
+}
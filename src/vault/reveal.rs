@@ -0,0 +1,246 @@
+//! Plaintext access to the main value of a secret
+//!
+//! `get_value` is for piping into other programs. `reveal_secret` is meant
+//! for showing a full value on purpose: the caller states why, and the reason
+//! goes to the audit log when it is enabled.
+//!
+//! The value is the password, key, note content, token, TOTP seed, private
+//! key, card number or WiFi password, depending on the type.
+
+use super::{Vault, VaultError};
+use crate::vault::types::{SecretType, SecretValue};
+
+impl Vault {
+    /// Returns the plain value of a secret, for scripts
+    pub fn get_value(
+        &self,
+        secret_type: SecretType,
+        id_or_name: &str,
+    ) -> Result<String, VaultError> {
+        let (_, value) = self.secret_value(secret_type, id_or_name)?;
+        Ok(value.expose_secret().to_string())
+    }
+
+    /// Returns the plain value of a secret and logs the reveal with `reason`
+    ///
+    /// A blank reason is rejected. When audit logging is enabled and the
+    /// reveal cannot be logged, the value is not returned.
+    pub fn reveal_secret(
+        &self,
+        secret_type: SecretType,
+        id_or_name: &str,
+        reason: &str,
+    ) -> Result<String, VaultError> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(VaultError::RevealReasonRequired);
+        }
+
+        let (name, value) = self.secret_value(secret_type, id_or_name)?;
+        if let Some(logger) = &self.audit {
+            logger.log_reveal((secret_type, name), reason)?;
+        }
+        Ok(value.expose_secret().to_string())
+    }
+
+    /// Finds a secret and returns its name and main value
+    fn secret_value(
+        &self,
+        secret_type: SecretType,
+        id_or_name: &str,
+    ) -> Result<(&str, &SecretValue), VaultError> {
+        let found = match secret_type {
+            SecretType::Password => self
+                .get_password(id_or_name)
+                .map(|s| (&s.name, &s.password)),
+            SecretType::ApiKey => self.get_api_key(id_or_name).map(|s| (&s.name, &s.key)),
+            SecretType::Note => self.get_note(id_or_name).map(|s| (&s.name, &s.content)),
+            SecretType::DbCredential => self
+                .get_db_credential(id_or_name)
+                .map(|s| (&s.name, &s.password)),
+            SecretType::Token => self.get_token(id_or_name).map(|s| (&s.name, &s.token)),
+            SecretType::Totp => self.get_totp(id_or_name).map(|s| (&s.name, &s.secret)),
+            SecretType::SshKey => self
+                .get_ssh_key(id_or_name)
+                .map(|s| (&s.name, &s.private_key)),
+            SecretType::CreditCard => self
+                .get_credit_card(id_or_name)
+                .map(|s| (&s.name, &s.number)),
+            SecretType::WifiCredential => self
+                .get_wifi_credential(id_or_name)
+                .map(|s| (&s.name, &s.password)),
+        };
+        found
+            .map(|(name, value)| (name.as_str(), value))
+            .ok_or_else(|| VaultError::SecretNotFound(id_or_name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::log::{read_audit_log, AuditEvent, AuditLogger};
+    use crate::vault::test_util;
+    use crate::vault::types::*;
+
+    #[test]
+    fn test_reveal_returns_value_and_logs_reason() {
+        let (_dir, vault) = test_util::test_vault();
+        let logger = AuditLogger::for_vault(&vault.path);
+        let mut vault = vault.with_audit(logger.clone());
+        vault
+            .add_api_key(ApiKey::new(
+                "stripe".into(),
+                "sk_live_123".into(),
+                None,
+                None,
+            ))
+            .unwrap();
+
+        let value = vault
+            .reveal_secret(SecretType::ApiKey, "stripe", "rotating webhook config")
+            .unwrap();
+        assert_eq!(value, "sk_live_123");
+
+        let entry = read_audit_log(logger.path()).unwrap().pop().unwrap();
+        assert_eq!(entry.event, AuditEvent::Reveal);
+        assert_eq!(entry.secret_type, Some(SecretType::ApiKey));
+        assert_eq!(entry.name.as_deref(), Some("stripe"));
+        assert_eq!(entry.reason.as_deref(), Some("rotating webhook config"));
+    }
+
+    #[test]
+    fn test_reveal_requires_reason_and_existing_secret() {
+        let (_dir, mut vault) = test_util::test_vault();
+        vault
+            .add_api_key(ApiKey::new(
+                "stripe".into(),
+                "sk_live_123".into(),
+                None,
+                None,
+            ))
+            .unwrap();
+
+        assert!(matches!(
+            vault.reveal_secret(SecretType::ApiKey, "stripe", "  "),
+            Err(VaultError::RevealReasonRequired)
+        ));
+        assert!(matches!(
+            vault.reveal_secret(SecretType::Password, "stripe", "debugging"),
+            Err(VaultError::SecretNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_reveal_fails_when_audit_log_is_unwritable() {
+        let (dir, vault) = test_util::test_vault();
+        let logger = AuditLogger::new(dir.path().join("missing").join("audit.log"));
+        let mut vault = vault.with_audit(logger);
+        vault
+            .add_api_key(ApiKey::new(
+                "stripe".into(),
+                "sk_live_123".into(),
+                None,
+                None,
+            ))
+            .unwrap();
+
+        assert!(matches!(
+            vault.reveal_secret(SecretType::ApiKey, "stripe", "debugging"),
+            Err(VaultError::AuditError(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_value_returns_main_field_of_each_type() {
+        let (_dir, mut vault) = test_util::test_vault();
+        vault
+            .add_password(Password::new(
+                "pw".into(),
+                "password-value".into(),
+                None,
+                None,
+                None,
+            ))
+            .unwrap();
+        vault
+            .add_api_key(ApiKey::new("key".into(), "key-value".into(), None, None))
+            .unwrap();
+        vault
+            .add_note(Note::new("note".into(), "note-value".into()))
+            .unwrap();
+        vault
+            .add_db_credential(DbCredential::new(
+                "db".into(),
+                "localhost".into(),
+                None,
+                "app".into(),
+                "admin".into(),
+                "db-value".into(),
+                None,
+                None,
+            ))
+            .unwrap();
+        vault
+            .add_token(Token::new(
+                "tok".into(),
+                "token-value".into(),
+                None,
+                None,
+                None,
+            ))
+            .unwrap();
+        vault
+            .add_totp(Totp::new("2fa".into(), "GEZDGNBVGY3TQOJQ".into(), None))
+            .unwrap();
+        vault
+            .add_ssh_key(SshKey::new(
+                "ssh".into(),
+                SshKeyType::Ed25519,
+                "ssh-ed25519 AAAA".into(),
+                "private-value".into(),
+                None,
+                None,
+            ))
+            .unwrap();
+        vault
+            .add_credit_card(CreditCard::new(
+                "card".into(),
+                "J Doe".into(),
+                "4111111111111111".into(),
+                12,
+                2099,
+                "123".into(),
+                None,
+            ))
+            .unwrap();
+        vault
+            .add_wifi_credential(WifiCredential::new(
+                "wifi".into(),
+                "HomeNet".into(),
+                "wifi-value".into(),
+                WifiSecurity::Wpa2,
+                false,
+            ))
+            .unwrap();
+
+        let cases = [
+            (SecretType::Password, "pw", "password-value"),
+            (SecretType::ApiKey, "key", "key-value"),
+            (SecretType::Note, "note", "note-value"),
+            (SecretType::DbCredential, "db", "db-value"),
+            (SecretType::Token, "tok", "token-value"),
+            (SecretType::Totp, "2fa", "GEZDGNBVGY3TQOJQ"),
+            (SecretType::SshKey, "ssh", "private-value"),
+            (SecretType::CreditCard, "card", "4111111111111111"),
+            (SecretType::WifiCredential, "wifi", "wifi-value"),
+        ];
+        for (secret_type, name, expected) in cases {
+            assert_eq!(vault.get_value(secret_type, name).unwrap(), expected);
+        }
+        assert!(matches!(
+            vault.get_value(SecretType::Token, "pw"),
+            Err(VaultError::SecretNotFound(_))
+        ));
+    }
+}
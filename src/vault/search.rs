@@ -0,0 +1,433 @@
+//! Full-text search over secret metadata
+//!
+//! Search only looks at descriptive fields (names, descriptions, usernames,
+//! URLs, ...). Secret values such as passwords, keys and tokens are never
+//! matched so a query can't be used to probe for them.
+//!
+//! Results are ranked by how well the name matches (exact, prefix, substring)
+//! with matches on other fields last.
+//!
+//! `suggest` offers "did you mean" candidates for a mistyped name, ranked by
+//! case-insensitive Levenshtein distance.
+
+use super::types::*;
+use super::{Vault, VaultData};
+
+/// How many "did you mean" candidates the CLI shows
+pub const SUGGESTION_COUNT: usize = 3;
+
+/// How well a secret matched a query, best first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchRank {
+    ExactName,
+    NamePrefix,
+    NameSubstring,
+    Field,
+}
+
+/// A secret matched by a search query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub secret_type: SecretType,
+    pub id: String,
+    pub name: String,
+    /// Name of the first field that matched (e.g. "name", "description")
+    pub field: &'static str,
+    pub rank: MatchRank,
+}
+
+/// Query plus optional type scope and result cap for `Vault::search_with`
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    pub query: String,
+    /// Only search these types; `None` searches all of them
+    pub types: Option<Vec<SecretType>>,
+    /// Maximum number of hits; `None` returns all of them
+    pub limit: Option<usize>,
+}
+
+impl SearchOptions {
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Searchable fields of a single secret
+struct Searchable<'a> {
+    secret_type: SecretType,
+    id: &'a str,
+    name: &'a str,
+    fields: Vec<(&'static str, Option<&'a str>)>,
+}
+
+impl Vault {
+    /// Case-insensitive substring search across the metadata of all secrets, best matches first
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        self.search_with(&SearchOptions::new(query))
+    }
+
+    /// Ranked search limited to the types and number of hits in `options`
+    pub fn search_with(&self, options: &SearchOptions) -> Vec<SearchHit> {
+        let query = options.query.trim().to_lowercase();
+        if query.is_empty() || options.limit == Some(0) {
+            return Vec::new();
+        }
+
+        let mut hits: Vec<SearchHit> = searchables(&self.data)
+            .into_iter()
+            .filter(|s| {
+                options
+                    .types
+                    .as_ref()
+                    .is_none_or(|types| types.contains(&s.secret_type))
+            })
+            .filter_map(|s| {
+                let (field, rank) = match_rank(&s, &query)?;
+                Some(SearchHit {
+                    secret_type: s.secret_type,
+                    id: s.id.to_string(),
+                    name: s.name.to_string(),
+                    field,
+                    rank,
+                })
+            })
+            .collect();
+        // Stable, so equally ranked hits keep type order
+        hits.sort_by_key(|hit| hit.rank);
+        if let Some(limit) = options.limit {
+            hits.truncate(limit);
+        }
+        hits
+    }
+
+    /// Returns up to `max` secret names closest to `query`, nearest first
+    ///
+    /// Names further than about a third of the query length away are left
+    /// out so unrelated secrets are never suggested.
+    pub fn suggest(&self, query: &str, max: usize) -> Vec<(SecretType, String)> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let cutoff = query.chars().count() / 3 + 1;
+
+        let mut candidates: Vec<(usize, SecretType, &str)> = self
+            .data
+            .entries()
+            .into_iter()
+            .map(|e| {
+                (
+                    levenshtein(&query, &e.name().to_lowercase()),
+                    e.secret_type(),
+                    e.name(),
+                )
+            })
+            .filter(|(distance, _, _)| *distance <= cutoff)
+            .collect();
+        candidates.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.2.cmp(b.2)));
+
+        candidates
+            .into_iter()
+            .take(max)
+            .map(|(_, secret_type, name)| (secret_type, name.to_string()))
+            .collect()
+    }
+}
+
+/// Best rank of `query` (already lowercased) against a secret and the field it matched
+fn match_rank(s: &Searchable<'_>, query: &str) -> Option<(&'static str, MatchRank)> {
+    let name = s.name.to_lowercase();
+    if name == query {
+        return Some(("name", MatchRank::ExactName));
+    }
+    if name.starts_with(query) {
+        return Some(("name", MatchRank::NamePrefix));
+    }
+    if name.contains(query) {
+        return Some(("name", MatchRank::NameSubstring));
+    }
+    s.fields
+        .iter()
+        .find(|(_, value)| value.is_some_and(|v| v.to_lowercase().contains(query)))
+        .map(|(field, _)| (*field, MatchRank::Field))
+}
+
+/// Number of single-character edits needed to turn `a` into `b`
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+fn searchables(data: &VaultData) -> Vec<Searchable<'_>> {
+    let mut items = Vec::new();
+
+    for p in &data.passwords {
+        items.push(Searchable {
+            secret_type: SecretType::Password,
+            id: &p.id,
+            name: &p.name,
+            fields: vec![
+                ("description", p.description.as_deref()),
+                ("username", p.username.as_deref()),
+                ("url", p.url.as_deref()),
+            ],
+        });
+    }
+    for k in &data.api_keys {
+        items.push(Searchable {
+            secret_type: SecretType::ApiKey,
+            id: &k.id,
+            name: &k.name,
+            fields: vec![
+                ("description", k.description.as_deref()),
+                ("service", k.service.as_deref()),
+            ],
+        });
+    }
+    for n in &data.notes {
+        items.push(Searchable {
+            secret_type: SecretType::Note,
+            id: &n.id,
+            name: &n.name,
+            fields: vec![
+                ("description", n.description.as_deref()),
+                ("content", Some(n.content.expose_secret())),
+            ],
+        });
+    }
+    for c in &data.db_credentials {
+        items.push(Searchable {
+            secret_type: SecretType::DbCredential,
+            id: &c.id,
+            name: &c.name,
+            fields: vec![
+                ("description", c.description.as_deref()),
+                ("host", Some(&c.host)),
+                ("database", Some(&c.database)),
+                ("username", Some(&c.username)),
+            ],
+        });
+    }
+    for t in &data.tokens {
+        items.push(Searchable {
+            secret_type: SecretType::Token,
+            id: &t.id,
+            name: &t.name,
+            fields: vec![
+                ("description", t.description.as_deref()),
+                ("token_type", t.token_type.as_deref()),
+            ],
+        });
+    }
+    for t in &data.totps {
+        items.push(Searchable {
+            secret_type: SecretType::Totp,
+            id: &t.id,
+            name: &t.name,
+            fields: vec![("issuer", t.issuer.as_deref())],
+        });
+    }
+    for k in &data.ssh_keys {
+        items.push(Searchable {
+            secret_type: SecretType::SshKey,
+            id: &k.id,
+            name: &k.name,
+            fields: vec![
+                ("description", k.description.as_deref()),
+                ("public_key", Some(&k.public_key)),
+            ],
+        });
+    }
+    for c in &data.credit_cards {
+        items.push(Searchable {
+            secret_type: SecretType::CreditCard,
+            id: &c.id,
+            name: &c.name,
+            fields: vec![
+                ("cardholder", Some(&c.cardholder)),
+                ("brand", c.brand.as_deref()),
+            ],
+        });
+    }
+    for w in &data.wifi_credentials {
+        items.push(Searchable {
+            secret_type: SecretType::WifiCredential,
+            id: &w.id,
+            name: &w.name,
+            fields: vec![("ssid", Some(&w.ssid))],
+        });
+    }
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use tempfile::TempDir;
+
+    fn sample_vault() -> (TempDir, Vault) {
+        let (dir, mut vault) = test_util::empty_vault();
+        vault.data.passwords.push(Password::new(
+            "github".into(),
+            "hunter2-secret".into(),
+            Some("Personal account".into()),
+            Some("octocat".into()),
+            Some("https://github.com".into()),
+        ));
+        vault.data.api_keys.push(ApiKey::new(
+            "stripe".into(),
+            "sk_live_secret".into(),
+            Some("Billing for the shop".into()),
+            Some("Stripe".into()),
+        ));
+        vault.data.notes.push(Note::new(
+            "recovery".into(),
+            "backup codes for github".into(),
+        ));
+        vault.data.db_credentials.push(DbCredential::new(
+            "prod-db".into(),
+            "db.example.com".into(),
+            None,
+            "shop".into(),
+            "admin".into(),
+            "db-secret".into(),
+            None,
+            None,
+        ));
+        (dir, vault)
+    }
+
+    #[test]
+    fn test_search_matches_description() {
+        let (_dir, vault) = sample_vault();
+
+        let hits = vault.search("BILLING");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].secret_type, SecretType::ApiKey);
+        assert_eq!(hits[0].name, "stripe");
+        assert_eq!(hits[0].field, "description");
+    }
+
+    #[test]
+    fn test_search_across_types() {
+        let (_dir, vault) = sample_vault();
+
+        let hits = vault.search("github");
+        let fields: Vec<_> = hits.iter().map(|h| (h.secret_type, h.field)).collect();
+        assert_eq!(
+            fields,
+            vec![
+                (SecretType::Password, "name"),
+                (SecretType::Note, "content"),
+            ]
+        );
+
+        let hits = vault.search("example.com");
+        assert_eq!(hits[0].field, "host");
+    }
+
+    #[test]
+    fn test_search_never_matches_secret_values() {
+        let (_dir, vault) = sample_vault();
+
+        assert!(vault.search("hunter2").is_empty());
+        assert!(vault.search("sk_live").is_empty());
+        assert!(vault.search("db-secret").is_empty());
+        assert!(vault.search("   ").is_empty());
+    }
+
+    #[test]
+    fn test_exact_name_outranks_description_match() {
+        let (_dir, mut vault) = sample_vault();
+        vault.data.api_keys.push(ApiKey::new(
+            "deploy".into(),
+            "k".into(),
+            Some("used by the shop pipeline".into()),
+            None,
+        ));
+        vault
+            .data
+            .passwords
+            .push(Password::new("shop".into(), "pw".into(), None, None, None));
+        vault
+            .data
+            .tokens
+            .push(Token::new("shopify".into(), "t".into(), None, None, None));
+
+        let hits = vault.search("shop");
+        let ranked: Vec<_> = hits.iter().map(|h| (h.name.as_str(), h.rank)).collect();
+        assert_eq!(ranked[0], ("shop", MatchRank::ExactName));
+        assert_eq!(ranked[1], ("shopify", MatchRank::NamePrefix));
+        assert!(ranked[2..]
+            .iter()
+            .all(|(_, rank)| *rank == MatchRank::Field));
+        assert!(ranked.contains(&("deploy", MatchRank::Field)));
+    }
+
+    #[test]
+    fn test_search_options_scope_and_limit() {
+        let (_dir, vault) = sample_vault();
+
+        let options = SearchOptions {
+            query: "github".into(),
+            types: Some(vec![SecretType::Note]),
+            limit: None,
+        };
+        let hits = vault.search_with(&options);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].secret_type, SecretType::Note);
+
+        let options = SearchOptions {
+            limit: Some(1),
+            ..SearchOptions::new("github")
+        };
+        let hits = vault.search_with(&options);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].secret_type, SecretType::Password);
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("github", "github"), 0);
+    }
+
+    #[test]
+    fn test_suggest_near_miss() {
+        let (_dir, mut vault) = sample_vault();
+        vault.data.passwords.push(Password::new(
+            "gitlab".into(),
+            "pw".into(),
+            None,
+            None,
+            None,
+        ));
+
+        let suggestions = vault.suggest("githb", 3);
+        assert_eq!(suggestions[0], (SecretType::Password, "github".to_string()));
+
+        assert_eq!(
+            vault.suggest("STRIP", 3),
+            vec![(SecretType::ApiKey, "stripe".to_string())]
+        );
+        assert!(vault.suggest("kubernetes", 3).is_empty());
+        assert_eq!(vault.suggest("gitxxb", 1).len(), 1);
+    }
+}
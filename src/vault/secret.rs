@@ -0,0 +1,137 @@
+//! Wrapper for secret-bearing string fields
+//!
+//! `SecretValue` keeps secret material out of `Debug`/`Display` output so that
+//! logging a whole record never leaks the value. Reading the plaintext requires
+//! an explicit call to [`SecretValue::expose_secret`].
+//!
+//! The value is zeroized when dropped; with the `secrecy` feature enabled it is
+//! held in a `secrecy::Secret<String>`, which takes care of that itself.
+
+use crate::crypto::constant_time_eq;
+use crate::utils::redact;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+#[cfg(feature = "secrecy")]
+use secrecy::ExposeSecret;
+
+#[cfg(feature = "secrecy")]
+type Inner = secrecy::Secret<String>;
+#[cfg(not(feature = "secrecy"))]
+type Inner = String;
+
+/// Placeholder printed instead of the secret value
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A secret string whose value is never printed by `Debug` or `Display`
+#[derive(Clone)]
+pub struct SecretValue(Inner);
+
+impl SecretValue {
+    /// Wraps a plaintext value
+    pub fn new(value: String) -> Self {
+        Self(Inner::from(value))
+    }
+
+    /// Returns the plaintext value
+    pub fn expose_secret(&self) -> &str {
+        #[cfg(feature = "secrecy")]
+        {
+            self.0.expose_secret()
+        }
+        #[cfg(not(feature = "secrecy"))]
+        {
+            &self.0
+        }
+    }
+
+    /// Returns true if the value is empty
+    pub fn is_empty(&self) -> bool {
+        self.expose_secret().is_empty()
+    }
+
+    /// Returns the value with all but `visible` characters at each end masked
+    pub fn redacted(&self, visible: usize) -> String {
+        redact::redact(self.expose_secret(), visible)
+    }
+}
+
+#[cfg(not(feature = "secrecy"))]
+impl Drop for SecretValue {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+impl From<String> for SecretValue {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for SecretValue {
+    fn from(value: &str) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+impl PartialEq for SecretValue {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(
+            self.expose_secret().as_bytes(),
+            other.expose_secret().as_bytes(),
+        )
+    }
+}
+
+impl Eq for SecretValue {}
+
+impl fmt::Debug for SecretValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl fmt::Display for SecretValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+// Serialized as a plain string so the vault format is unchanged
+impl Serialize for SecretValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.expose_secret())
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_and_display_are_redacted() {
+        let secret = SecretValue::from("hunter2");
+
+        assert_eq!(format!("{:?}", secret), REDACTED);
+        assert_eq!(format!("{}", secret), REDACTED);
+        assert_eq!(secret.expose_secret(), "hunter2");
+    }
+
+    #[test]
+    fn test_serializes_as_plain_string() {
+        let secret = SecretValue::from("hunter2");
+
+        let json = serde_json::to_string(&secret).unwrap();
+        assert_eq!(json, "\"hunter2\"");
+
+        let parsed: SecretValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, secret);
+    }
+}
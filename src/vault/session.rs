@@ -0,0 +1,111 @@
+//! Idle timeout for long-running sessions
+//!
+//! `SessionGuard` owns an unlocked `Vault` and locks it (wiping the key) once
+//! it has gone unused for longer than the configured timeout. Every access
+//! through the guard counts as activity.
+
+use super::{Vault, VaultError};
+use std::time::{Duration, Instant};
+
+/// An unlocked vault that re-locks itself after a period of inactivity
+pub struct SessionGuard {
+    vault: Vault,
+    timeout: Duration,
+    last_activity: Instant,
+}
+
+impl SessionGuard {
+    /// Wraps a vault; the idle timer starts now
+    pub fn new(vault: Vault, timeout: Duration) -> Self {
+        Self {
+            vault,
+            timeout,
+            last_activity: Instant::now(),
+        }
+    }
+
+    /// Returns the vault, or `SessionExpired` if it has been idle too long
+    pub fn vault(&mut self) -> Result<&Vault, VaultError> {
+        self.check()?;
+        Ok(&self.vault)
+    }
+
+    /// Returns the vault mutably, or `SessionExpired` if it has been idle too long
+    pub fn vault_mut(&mut self) -> Result<&mut Vault, VaultError> {
+        self.check()?;
+        Ok(&mut self.vault)
+    }
+
+    /// Resets the idle timer
+    pub fn touch(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// Unlocks the vault again after it expired
+    pub fn unlock(&mut self, master_password: &str) -> Result<(), VaultError> {
+        self.vault.unlock(master_password)?;
+        self.touch();
+        Ok(())
+    }
+
+    /// Returns true if the idle timeout has elapsed
+    pub fn is_expired(&self) -> bool {
+        self.last_activity.elapsed() >= self.timeout
+    }
+
+    /// Locks the vault if the timeout elapsed, otherwise records activity
+    fn check(&mut self) -> Result<(), VaultError> {
+        if self.is_expired() {
+            self.vault.lock();
+        }
+        if !self.vault.is_unlocked() {
+            return Err(VaultError::SessionExpired);
+        }
+        self.touch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use crate::vault::types::Note;
+    use std::thread::sleep;
+
+    #[test]
+    fn test_session_expires_after_idle_timeout() {
+        let (_dir, mut vault) = test_util::test_vault();
+        vault
+            .add_note(Note::new("codes".into(), "111".into()))
+            .unwrap();
+
+        let mut session = SessionGuard::new(vault, Duration::from_millis(50));
+        assert!(session.vault().unwrap().get_note("codes").is_some());
+
+        sleep(Duration::from_millis(80));
+        assert!(session.is_expired());
+        assert!(matches!(session.vault(), Err(VaultError::SessionExpired)));
+
+        // Stays locked even though the last (failed) access was just now
+        assert!(matches!(
+            session.vault_mut(),
+            Err(VaultError::SessionExpired)
+        ));
+
+        session.unlock(test_util::PASSWORD).unwrap();
+        assert!(session.vault().unwrap().get_note("codes").is_some());
+    }
+
+    #[test]
+    fn test_touch_extends_session() {
+        let (_dir, vault) = test_util::test_vault();
+
+        let mut session = SessionGuard::new(vault, Duration::from_millis(500));
+        for _ in 0..3 {
+            sleep(Duration::from_millis(200));
+            session.touch();
+        }
+        assert!(session.vault().is_ok());
+    }
+}
@@ -0,0 +1,95 @@
+//! Summary statistics over the unlocked vault
+
+use super::types::*;
+use super::Vault;
+use chrono::{DateTime, Utc};
+
+/// Counts and dates describing the vault contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultStats {
+    /// Number of secrets per type, in `SecretType::ALL` order
+    pub counts: Vec<(SecretType, usize)>,
+    pub total: usize,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
+    pub expired_tokens: usize,
+}
+
+impl VaultStats {
+    /// Returns the number of secrets of one type
+    pub fn count(&self, secret_type: SecretType) -> usize {
+        self.counts
+            .iter()
+            .find(|(t, _)| *t == secret_type)
+            .map_or(0, |(_, n)| *n)
+    }
+}
+
+impl Vault {
+    /// Summarises the in-memory data (the vault file is not re-read)
+    pub fn stats(&self) -> VaultStats {
+        let entries = self.data.entries();
+        let counts = SecretType::ALL
+            .iter()
+            .map(|t| (*t, entries.iter().filter(|e| e.secret_type() == *t).count()))
+            .collect();
+
+        VaultStats {
+            counts,
+            total: entries.len(),
+            oldest: entries.iter().map(|e| e.created_at()).min(),
+            newest: entries.iter().map(|e| e.created_at()).max(),
+            expired_tokens: self.expired_tokens().len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use chrono::Duration;
+
+    #[test]
+    fn test_stats_counts() {
+        let (_dir, mut vault) = test_util::empty_vault();
+        let empty = vault.stats();
+        assert_eq!(empty.total, 0);
+        assert_eq!(empty.oldest, None);
+
+        let mut old = Password::new("old".into(), "pw".into(), None, None, None);
+        old.created_at -= Duration::days(10);
+        let oldest = old.created_at;
+        vault.data.passwords = vec![
+            old,
+            Password::new("new".into(), "pw".into(), None, None, None),
+        ];
+        vault.data.api_keys = vec![
+            ApiKey::new("a".into(), "k".into(), None, None),
+            ApiKey::new("b".into(), "k".into(), None, None),
+        ];
+        vault.data.notes = vec![Note::new("n".into(), "c".into())];
+        vault.data.tokens = vec![
+            Token::new(
+                "expired".into(),
+                "t".into(),
+                None,
+                None,
+                Some(Utc::now() - Duration::days(1)),
+            ),
+            Token::new("valid".into(), "t".into(), None, None, None),
+        ];
+        let newest = vault.data.tokens[1].created_at;
+
+        let stats = vault.stats();
+        assert_eq!(stats.count(SecretType::Password), 2);
+        assert_eq!(stats.count(SecretType::ApiKey), 2);
+        assert_eq!(stats.count(SecretType::Note), 1);
+        assert_eq!(stats.count(SecretType::Token), 2);
+        assert_eq!(stats.count(SecretType::CreditCard), 0);
+        assert_eq!(stats.total, 7);
+        assert_eq!(stats.expired_tokens, 1);
+        assert_eq!(stats.oldest, Some(oldest));
+        assert_eq!(stats.newest, Some(newest));
+    }
+}
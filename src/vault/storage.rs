@@ -1,8 +1,15 @@
 //! Vault storage operations
 
 use super::{VaultError, VaultFile};
-use std::fs;
-use std::path::PathBuf;
+use crate::config::Config;
+use crate::crypto::{kdf, Algorithm};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
 
 /// Returns the default vault directory path
 pub fn get_vault_dir() -> PathBuf {
@@ -11,9 +18,82 @@ pub fn get_vault_dir() -> PathBuf {
         .join(".kookie")
 }
 
-/// Returns the default vault file path
-pub fn get_vault_path() -> PathBuf {
-    get_vault_dir().join("vault.json")
+/// Environment variable that overrides the vault file location
+pub const VAULT_PATH_ENV: &str = "KOOKIE_VAULT_PATH";
+
+/// Returns the vault file path
+///
+/// `KOOKIE_VAULT_PATH` takes precedence when set and non-empty, e.g. to keep
+/// the vault on an encrypted volume. Otherwise this is the vault of the
+/// `default_profile` from `kookie.toml`, which must be a valid profile name.
+pub fn get_vault_path() -> Result<PathBuf, VaultError> {
+    vault_path_from(std::env::var_os(VAULT_PATH_ENV))
+}
+
+/// `get_vault_path` given the value of `KOOKIE_VAULT_PATH`
+fn vault_path_from(env: Option<OsString>) -> Result<PathBuf, VaultError> {
+    match env {
+        Some(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+        _ => get_vault_path_for(&Config::current().default_profile),
+    }
+}
+
+/// Name of the profile stored in the default vault file
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Returns the vault file path for a named profile
+///
+/// The default profile maps to `vault.json`; any other profile gets its own
+/// `vault-<profile>.json` next to it.
+pub fn get_vault_path_for(profile: &str) -> Result<PathBuf, VaultError> {
+    vault_path_in(&get_vault_dir(), profile)
+}
+
+/// Returns the names of all profiles that have a vault file
+pub fn list_profiles() -> Result<Vec<String>, VaultError> {
+    list_profiles_in(&get_vault_dir())
+}
+
+fn vault_path_in(dir: &Path, profile: &str) -> Result<PathBuf, VaultError> {
+    let valid = !profile.is_empty()
+        && profile
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(VaultError::InvalidProfile(profile.to_string()));
+    }
+
+    if profile == DEFAULT_PROFILE {
+        Ok(dir.join("vault.json"))
+    } else {
+        Ok(dir.join(format!("vault-{}.json", profile)))
+    }
+}
+
+fn list_profiles_in(dir: &Path) -> Result<Vec<String>, VaultError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut profiles = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let file_name = entry?.file_name();
+        let Some(name) = file_name.to_str() else {
+            continue;
+        };
+        if name == "vault.json" {
+            profiles.push(DEFAULT_PROFILE.to_string());
+        } else if let Some(profile) = name
+            .strip_prefix("vault-")
+            .and_then(|rest| rest.strip_suffix(".json"))
+        {
+            if vault_path_in(dir, profile).is_ok() {
+                profiles.push(profile.to_string());
+            }
+        }
+    }
+    profiles.sort();
+    Ok(profiles)
 }
 
 /// Returns the session file path
@@ -35,17 +115,323 @@ pub fn ensure_vault_dir() -> Result<(), VaultError> {
     Ok(())
 }
 
+/// Exclusive advisory lock on a vault, held until dropped
+///
+/// The lock is taken on a `<vault>.lock` sidecar rather than the vault itself
+/// because saves replace the vault file by renaming over it. The holder's PID
+/// is written into the sidecar so a blocked process can report who has it.
+#[derive(Debug)]
+pub struct VaultLock {
+    file: File,
+}
+
+impl VaultLock {
+    /// Tries to lock the vault at `vault_path` without blocking
+    pub fn acquire(vault_path: &Path) -> Result<Self, VaultError> {
+        let lock_path = lock_path(vault_path);
+        if let Some(dir) = lock_path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut content = String::new();
+                let _ = file.read_to_string(&mut content);
+                return Err(VaultError::Locked(content.trim().parse().ok()));
+            }
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+        }
+
+        file.set_len(0)?;
+        file.rewind()?;
+        write!(file, "{}", std::process::id())?;
+        Ok(Self { file })
+    }
+}
+
+impl Drop for VaultLock {
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+/// Returns the lock sidecar path for a vault file
+fn lock_path(vault_path: &Path) -> PathBuf {
+    let mut name = vault_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    vault_path.with_file_name(name)
+}
+
 /// Loads the vault file from disk
-pub fn load_vault_file(path: &PathBuf) -> Result<VaultFile, VaultError> {
+pub fn load_vault_file(path: &Path) -> Result<VaultFile, VaultError> {
     let content = fs::read_to_string(path)?;
     let vault_file: VaultFile = serde_json::from_str(&content)?;
     Ok(vault_file)
 }
 
+/// Unencrypted envelope fields of a vault file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultHeader {
+    pub version: u32,
+    pub algorithm: Algorithm,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+    /// Length of the base64-encoded salt; the salt itself is not exposed
+    pub salt_len: usize,
+    /// Decoded salt size, or `None` if it isn't valid `kdf::SALT_ENCODING`
+    pub salt_bytes: Option<usize>,
+}
+
+/// `VaultFile` without `encrypted_data`, which serde skips over
+#[derive(Deserialize)]
+struct Envelope {
+    version: u32,
+    salt: String,
+    #[serde(default)]
+    algorithm: Option<Algorithm>,
+    created_at: DateTime<Utc>,
+    modified_at: DateTime<Utc>,
+    #[serde(default)]
+    failed_attempts: u32,
+    #[serde(default)]
+    lockout_until: Option<DateTime<Utc>>,
+}
+
+fn read_envelope(path: &Path) -> Result<Envelope, VaultError> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Reads the vault's version and timestamps without the master password
+pub fn read_vault_header(path: &Path) -> Result<VaultHeader, VaultError> {
+    let envelope = read_envelope(path)?;
+    Ok(VaultHeader {
+        version: envelope.version,
+        algorithm: envelope.algorithm.unwrap_or_default(),
+        created_at: envelope.created_at,
+        modified_at: envelope.modified_at,
+        salt_len: envelope.salt.len(),
+        salt_bytes: kdf::decode_salt(&envelope.salt).ok().map(|b| b.len()),
+    })
+}
+
+/// Failed attempts and cooldown recorded in the file at `path` for `salt`
+///
+/// Other processes count wrong passwords in the file while this one holds
+/// the vault open, so saves carry these forward. A missing or unreadable
+/// file, or one re-created with another salt, has nothing recorded.
+pub(super) fn read_lockout_state(path: &Path, salt: &str) -> (u32, Option<DateTime<Utc>>) {
+    match read_envelope(path) {
+        Ok(envelope) if envelope.salt == salt => (envelope.failed_attempts, envelope.lockout_until),
+        _ => (0, None),
+    }
+}
+
 /// Saves the vault file to disk
-pub fn save_vault_file(path: &PathBuf, vault_file: &VaultFile) -> Result<(), VaultError> {
-    ensure_vault_dir()?;
+pub fn save_vault_file(path: &Path, vault_file: &VaultFile) -> Result<(), VaultError> {
     let content = serde_json::to_string_pretty(vault_file)?;
-    fs::write(path, content)?;
+    write_atomic(path, content.as_bytes())?;
+    Ok(())
+}
+
+/// Writes a file atomically
+///
+/// The content goes to a temporary file in the same directory, which is
+/// fsynced and then renamed over the target. A crash at any point leaves
+/// either the old file or the new one, never a truncated mix.
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<(), std::io::Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir)?;
+
+    let mut temp = NamedTempFile::new_in(dir)?;
+    temp.write_all(content)?;
+    temp.as_file().sync_all()?;
+    temp.persist(path).map_err(|e| e.error)?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::{test_util, Vault};
+    use chrono::Utc;
+
+    fn sample_file(encrypted_data: &str) -> VaultFile {
+        VaultFile {
+            version: 1,
+            salt: "c2FsdHNhbHRzYWx0c2FsdA".into(),
+            kdf_params: None,
+            algorithm: None,
+            chunked: false,
+            encrypted_data: encrypted_data.into(),
+            created_at: Utc::now(),
+            modified_at: Utc::now(),
+            lockout_policy: None,
+            failed_attempts: 0,
+            lockout_until: None,
+            wrapped_key: None,
+            recovery: None,
+            raw_password: false,
+        }
+    }
+
+    #[test]
+    fn test_read_header_of_new_vault() {
+        let (_dir, vault) = test_util::test_vault();
+
+        let header = read_vault_header(&vault.path).unwrap();
+        let file = load_vault_file(&vault.path).unwrap();
+        assert_eq!(header.version, crate::vault::VAULT_VERSION);
+        assert_eq!(header.algorithm, Algorithm::default());
+        assert_eq!(header.created_at, file.created_at);
+        assert_eq!(header.modified_at, file.modified_at);
+        assert_eq!(header.salt_len, file.salt.len());
+        assert!(header.salt_len >= 22);
+        assert_eq!(header.salt_bytes, Some(kdf::SALT_LEN));
+    }
+
+    #[test]
+    fn test_read_header_ignores_encrypted_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+        save_vault_file(&path, &sample_file("not even base64")).unwrap();
+
+        let header = read_vault_header(&path).unwrap();
+        assert_eq!(header.version, 1);
+        assert_eq!(header.salt_len, 22);
+    }
+
+    #[test]
+    fn test_save_replaces_file_without_leftovers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+
+        save_vault_file(&path, &sample_file("first")).unwrap();
+        save_vault_file(&path, &sample_file("second")).unwrap();
+
+        assert_eq!(load_vault_file(&path).unwrap().encrypted_data, "second");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_interrupted_write_leaves_original_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+        save_vault_file(&path, &sample_file("original")).unwrap();
+
+        // Simulate a crash mid-save: the temp file is written but never renamed
+        let mut temp = NamedTempFile::new_in(dir.path()).unwrap();
+        temp.write_all(b"{\"version\": 1, \"salt\": \"trunc")
+            .unwrap();
+        drop(temp);
+
+        assert_eq!(load_vault_file(&path).unwrap().encrypted_data, "original");
+    }
+
+    #[test]
+    fn test_profile_paths() {
+        let dir = Path::new("/config");
+        assert_eq!(
+            vault_path_in(dir, DEFAULT_PROFILE).unwrap(),
+            dir.join("vault.json")
+        );
+        assert_eq!(
+            vault_path_in(dir, "work").unwrap(),
+            dir.join("vault-work.json")
+        );
+        for bad in ["", "../etc", "a/b", "has space"] {
+            assert!(matches!(
+                vault_path_in(dir, bad),
+                Err(VaultError::InvalidProfile(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_list_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(list_profiles_in(dir.path()).unwrap().is_empty());
+
+        for profile in [DEFAULT_PROFILE, "work", "personal"] {
+            let path = vault_path_in(dir.path(), profile).unwrap();
+            save_vault_file(&path, &sample_file(profile)).unwrap();
+        }
+        fs::write(dir.path().join("config.json"), "{}").unwrap();
+
+        assert_eq!(
+            list_profiles_in(dir.path()).unwrap(),
+            vec!["default", "personal", "work"]
+        );
+    }
+
+    #[test]
+    fn test_profiles_init_independently() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut default = Vault::with_path(vault_path_in(dir.path(), DEFAULT_PROFILE).unwrap());
+        let mut work = Vault::with_path(vault_path_in(dir.path(), "work").unwrap());
+
+        default.init("default-pw").unwrap();
+        work.init("work-pw").unwrap();
+
+        let mut reopened = Vault::with_path(default.path.clone());
+        reopened.unlock("default-pw").unwrap();
+        let mut reopened = Vault::with_path(work.path.clone());
+        reopened.unlock("work-pw").unwrap();
+    }
+
+    #[test]
+    fn test_vault_path_env_override() {
+        let path = PathBuf::from("/mnt/secure/my-vault.json");
+        assert_eq!(vault_path_from(Some(path.clone().into())).unwrap(), path);
+
+        // Unset or empty means the default profile's vault
+        let default = get_vault_path_for(&Config::current().default_profile).unwrap();
+        assert_eq!(vault_path_from(None).unwrap(), default);
+        assert_eq!(vault_path_from(Some(OsString::new())).unwrap(), default);
+    }
+
+    #[test]
+    fn test_lock_contention_between_threads() {
+        use std::sync::mpsc;
+        use std::thread;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+        let (locked_tx, locked_rx) = mpsc::channel();
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+
+        let holder_path = path.clone();
+        let holder = thread::spawn(move || {
+            let _lock = VaultLock::acquire(&holder_path).unwrap();
+            locked_tx.send(()).unwrap();
+            done_rx.recv().unwrap();
+        });
+
+        locked_rx.recv().unwrap();
+        let contender_path = path.clone();
+        let result = thread::spawn(move || VaultLock::acquire(&contender_path))
+            .join()
+            .unwrap();
+        assert!(matches!(
+            result,
+            Err(VaultError::Locked(Some(pid))) if pid == std::process::id()
+        ));
+
+        done_tx.send(()).unwrap();
+        holder.join().unwrap();
+        assert!(VaultLock::acquire(&path).is_ok());
+    }
+}
@@ -0,0 +1,117 @@
+//! Prefilled constructors for secrets of common services
+
+use crate::vault::types::{ApiKey, DbCredential, Token};
+
+impl ApiKey {
+    /// A Stripe secret key
+    pub fn for_stripe(key: impl Into<String>) -> Self {
+        Self::new(
+            "stripe".to_string(),
+            key.into(),
+            None,
+            Some("Stripe".to_string()),
+        )
+    }
+
+    /// An OpenAI API key
+    pub fn for_openai(key: impl Into<String>) -> Self {
+        Self::new(
+            "openai".to_string(),
+            key.into(),
+            None,
+            Some("OpenAI".to_string()),
+        )
+    }
+}
+
+impl Token {
+    /// A GitHub personal access token
+    pub fn github_pat(token: impl Into<String>) -> Self {
+        Self::new(
+            "github-pat".to_string(),
+            token.into(),
+            Some("GitHub personal access token".to_string()),
+            Some("github-pat".to_string()),
+            None,
+        )
+    }
+}
+
+impl DbCredential {
+    /// A Postgres database on localhost, named after the database
+    pub fn postgres_local(
+        database: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        local_db(
+            "postgres",
+            database.into(),
+            username.into(),
+            password.into(),
+        )
+    }
+
+    /// A MySQL database on localhost, named after the database
+    pub fn mysql_local(
+        database: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        local_db("mysql", database.into(), username.into(), password.into())
+    }
+}
+
+fn local_db(db_type: &str, database: String, username: String, password: String) -> DbCredential {
+    DbCredential::new(
+        database.clone(),
+        "localhost".to_string(),
+        Some(DbCredential::default_port(db_type)),
+        database,
+        username,
+        password,
+        Some(db_type.to_string()),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_templates() {
+        let stripe = ApiKey::for_stripe("sk_live_123");
+        assert_eq!(stripe.name, "stripe");
+        assert_eq!(stripe.service.as_deref(), Some("Stripe"));
+        assert_eq!(stripe.key.expose_secret(), "sk_live_123");
+
+        let openai = ApiKey::for_openai("sk-abc");
+        assert_eq!(openai.name, "openai");
+        assert_eq!(openai.service.as_deref(), Some("OpenAI"));
+    }
+
+    #[test]
+    fn test_github_pat_template() {
+        let token = Token::github_pat("ghp_abc");
+        assert_eq!(token.name, "github-pat");
+        assert_eq!(token.token_type.as_deref(), Some("github-pat"));
+        assert_eq!(token.token.expose_secret(), "ghp_abc");
+        assert!(token.expires_at.is_none());
+    }
+
+    #[test]
+    fn test_local_db_templates() {
+        let pg = DbCredential::postgres_local("app", "alice", "secret");
+        assert_eq!(pg.name, "app");
+        assert_eq!(pg.host, "localhost");
+        assert_eq!(pg.port, Some(5432));
+        assert_eq!(pg.database, "app");
+        assert_eq!(pg.username, "alice");
+        assert_eq!(pg.db_type.as_deref(), Some("postgres"));
+
+        let my = DbCredential::mysql_local("shop", "bob", "secret");
+        assert_eq!(my.port, Some(3306));
+        assert_eq!(my.db_type.as_deref(), Some("mysql"));
+    }
+}
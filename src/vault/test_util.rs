@@ -0,0 +1,42 @@
+//! Fixtures shared by the vault tests
+
+use super::Vault;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// Master password of the vaults created here
+pub(crate) const PASSWORD: &str = "pw";
+
+/// An unlocked vault initialized with `PASSWORD` in a fresh temp dir
+///
+/// Keep the `TempDir` alive for as long as the vault is used.
+pub(crate) fn test_vault() -> (TempDir, Vault) {
+    let dir = tempfile::tempdir().unwrap();
+    let vault = test_vault_at(dir.path().join("vault.json"));
+    (dir, vault)
+}
+
+/// An unlocked vault initialized with `PASSWORD` at `path`
+///
+/// For tests that need several vaults, or a fixed name next to the vault.
+pub(crate) fn test_vault_at(path: PathBuf) -> Vault {
+    let mut vault = Vault::with_path(path);
+    vault.init(PASSWORD).unwrap();
+    vault
+}
+
+/// A second handle on `vault`'s file, unlocked with `PASSWORD`
+pub(crate) fn reopen(vault: &Vault) -> Vault {
+    let mut reopened = Vault::with_path(vault.path.clone());
+    reopened.unlock(PASSWORD).unwrap();
+    reopened
+}
+
+/// A locked, uninitialized vault whose file would live in a fresh temp dir
+///
+/// For tests that only fill in `data` and never touch the file.
+pub(crate) fn empty_vault() -> (TempDir, Vault) {
+    let dir = tempfile::tempdir().unwrap();
+    let vault = Vault::with_path(dir.path().join("vault.json"));
+    (dir, vault)
+}
@@ -0,0 +1,105 @@
+//! Vault activity derived from secret timestamps
+//!
+//! Nothing extra is stored: each secret contributes a creation event and,
+//! if it was changed since, one update event for its latest change.
+
+use super::types::*;
+use super::Vault;
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// What happened to a secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineKind {
+    Created,
+    Updated,
+}
+
+impl fmt::Display for TimelineKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimelineKind::Created => write!(f, "created"),
+            TimelineKind::Updated => write!(f, "updated"),
+        }
+    }
+}
+
+/// One entry in the vault timeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineEvent {
+    pub at: DateTime<Utc>,
+    pub secret_type: SecretType,
+    pub name: String,
+    pub kind: TimelineKind,
+}
+
+impl Vault {
+    /// Returns creation and update events for every secret, oldest first
+    pub fn timeline(&self) -> Vec<TimelineEvent> {
+        let mut events = Vec::new();
+        for entry in self.data.entries() {
+            let event = |at, kind| TimelineEvent {
+                at,
+                secret_type: entry.secret_type(),
+                name: entry.name().to_string(),
+                kind,
+            };
+            events.push(event(entry.created_at(), TimelineKind::Created));
+            if entry.updated_at() > entry.created_at() {
+                events.push(event(entry.updated_at(), TimelineKind::Updated));
+            }
+        }
+        // Stable, so same-time events keep their collection order
+        events.sort_by_key(|e| e.at);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util;
+    use chrono::Duration;
+
+    #[test]
+    fn test_timeline_orders_events_across_types() {
+        let base = Utc::now() - Duration::days(30);
+        let (_dir, mut vault) = test_util::empty_vault();
+
+        let mut password = Password::new("github".into(), "pw".into(), None, None, None);
+        password.created_at = base;
+        password.updated_at = base + Duration::days(20);
+        let mut note = Note::new("memo".into(), "text".into());
+        note.created_at = base + Duration::days(5);
+        note.updated_at = note.created_at;
+        let mut token = Token::new("ci".into(), "t0k3n".into(), None, None, None);
+        token.created_at = base + Duration::days(10);
+        token.updated_at = base + Duration::days(15);
+        vault.data.passwords.push(password);
+        vault.data.notes.push(note);
+        vault.data.tokens.push(token);
+
+        let timeline = vault.timeline();
+        let events: Vec<(i64, SecretType, &str, TimelineKind)> = timeline
+            .iter()
+            .map(|e| {
+                (
+                    (e.at - base).num_days(),
+                    e.secret_type,
+                    e.name.as_str(),
+                    e.kind,
+                )
+            })
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (0, SecretType::Password, "github", TimelineKind::Created),
+                (5, SecretType::Note, "memo", TimelineKind::Created),
+                (10, SecretType::Token, "ci", TimelineKind::Created),
+                (15, SecretType::Token, "ci", TimelineKind::Updated),
+                (20, SecretType::Password, "github", TimelineKind::Updated),
+            ]
+        );
+    }
+}
@@ -0,0 +1,728 @@
+//! Export and import of vault contents
+//!
+//! Encrypted exports are self-contained `VaultFile`s protected by their own
+//! password and salt, so a backup can be restored on any machine.
+//!
+//! When imported secrets collide by name with existing ones (within the same
+//! secret type), the `ImportMode` decides what happens:
+//! - `Skip` keeps the existing secret and drops the incoming one
+//! - `Replace` overwrites the existing secret but keeps its id
+//! - `Rename` stores the incoming secret under a free name like `name (2)`
+//! - `NewestWins` keeps whichever of the two was updated last
+//!
+//! Incoming secrets whose id is already taken get a fresh id.
+//!
+//! `plan_import` previews an import without touching the vault: it sorts the
+//! incoming secrets into new ones, name collisions and exact duplicates.
+//!
+//! Plaintext JSON exports contain every secret value unencrypted and must be
+//! explicitly confirmed by the caller.
+
+use super::types::*;
+use super::{free_name, migrate, storage, Vault, VaultData, VaultError, VaultFile, VAULT_VERSION};
+use crate::audit::log::AuditEvent;
+use crate::crypto::kdf::{self, KdfParams};
+use crate::crypto::{self, Algorithm};
+use chrono::Utc;
+use std::path::Path;
+use uuid::Uuid;
+
+/// How to resolve name collisions when importing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportMode {
+    /// Replace existing secrets with the imported ones
+    Replace,
+    /// Keep existing secrets and ignore the imported ones
+    #[default]
+    Skip,
+    /// Keep both, renaming the imported secret
+    Rename,
+    /// Keep whichever secret has the later `updated_at`
+    NewestWins,
+}
+
+/// Summary of an import
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub added: usize,
+    pub skipped: usize,
+    pub replaced: usize,
+    pub renamed: usize,
+}
+
+/// Summary of a vault merge
+pub type MergeReport = ImportReport;
+
+impl ImportReport {
+    /// Total number of secrets written to the vault
+    pub fn imported(&self) -> usize {
+        self.added + self.replaced + self.renamed
+    }
+}
+
+/// What an import would do, per incoming secret, as (type, name)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportPlan {
+    /// No existing secret of that type has the name
+    pub new: Vec<(SecretType, String)>,
+    /// The name is taken by a secret with different contents
+    pub collisions: Vec<(SecretType, String)>,
+    /// The name is taken by a secret with the same contents
+    pub duplicates: Vec<(SecretType, String)>,
+}
+
+impl Vault {
+    /// Classifies `incoming` against the vault without changing or saving anything
+    pub fn plan_import(&self, incoming: &VaultData) -> ImportPlan {
+        let mut plan = ImportPlan::default();
+        plan_collection(&self.data.passwords, &incoming.passwords, &mut plan);
+        plan_collection(&self.data.api_keys, &incoming.api_keys, &mut plan);
+        plan_collection(&self.data.notes, &incoming.notes, &mut plan);
+        plan_collection(
+            &self.data.db_credentials,
+            &incoming.db_credentials,
+            &mut plan,
+        );
+        plan_collection(&self.data.tokens, &incoming.tokens, &mut plan);
+        plan_collection(&self.data.totps, &incoming.totps, &mut plan);
+        plan_collection(&self.data.ssh_keys, &incoming.ssh_keys, &mut plan);
+        plan_collection(&self.data.credit_cards, &incoming.credit_cards, &mut plan);
+        plan_collection(
+            &self.data.wifi_credentials,
+            &incoming.wifi_credentials,
+            &mut plan,
+        );
+        plan
+    }
+
+    /// Writes an encrypted copy of the vault data to `path`
+    ///
+    /// The export is protected by `password` with a freshly generated salt,
+    /// independent of the vault's own master password.
+    pub fn export_encrypted(&self, path: &Path, password: &str) -> Result<(), VaultError> {
+        write_encrypted(path, password, &self.data)?;
+        self.audit(AuditEvent::Export, None);
+        Ok(())
+    }
+
+    /// Like `export_encrypted`, but only includes secrets of the given types
+    ///
+    /// Useful for sharing e.g. just database credentials. The result is a
+    /// regular export that `import_encrypted` accepts.
+    pub fn export_encrypted_selective(
+        &self,
+        path: &Path,
+        password: &str,
+        types: &[SecretType],
+    ) -> Result<(), VaultError> {
+        write_encrypted(path, password, &select_types(&self.data, types))?;
+        self.audit(AuditEvent::Export, None);
+        Ok(())
+    }
+
+    /// Imports secrets from an encrypted export and saves the vault
+    pub fn import_encrypted(
+        &mut self,
+        path: &Path,
+        password: &str,
+        mode: ImportMode,
+    ) -> Result<ImportReport, VaultError> {
+        let before = self.data.clone();
+        let incoming = read_encrypted(path, password)?;
+        let report = merge_data(&mut self.data, incoming, mode);
+        self.save_change(before)?;
+        self.audit(AuditEvent::Import, None);
+        Ok(report)
+    }
+
+    /// Merges another vault's secrets into this one
+    ///
+    /// Only the in-memory data changes; call `save` to persist the result.
+    pub fn merge_from(&mut self, other: &VaultData, mode: ImportMode) -> MergeReport {
+        merge_data(&mut self.data, other.clone(), mode)
+    }
+
+    /// Returns all vault data, including secret values, as pretty JSON
+    ///
+    /// Fails with `ExportNotConfirmed` unless `confirm` is true.
+    pub fn export_plaintext_json(&self, confirm: bool) -> Result<String, VaultError> {
+        if !confirm {
+            return Err(VaultError::ExportNotConfirmed);
+        }
+        let json = serde_json::to_string_pretty(&self.data)?;
+        self.audit(AuditEvent::Export, None);
+        Ok(json)
+    }
+
+    /// Imports secrets from a plaintext JSON export and saves the vault
+    pub fn import_plaintext_json(
+        &mut self,
+        json: &str,
+        mode: ImportMode,
+    ) -> Result<ImportReport, VaultError> {
+        let before = self.data.clone();
+        let incoming: VaultData = serde_json::from_str(json)?;
+        let report = merge_data(&mut self.data, incoming, mode);
+        self.save_change(before)?;
+        self.audit(AuditEvent::Import, None);
+        Ok(report)
+    }
+
+    /// Adds passwords parsed by an importer and saves the vault
+    pub fn import_passwords(
+        &mut self,
+        passwords: Vec<Password>,
+        mode: ImportMode,
+    ) -> Result<ImportReport, VaultError> {
+        self.import_data(
+            VaultData {
+                passwords,
+                ..Default::default()
+            },
+            mode,
+        )
+    }
+
+    /// Merges secrets parsed by an importer and saves the vault
+    pub fn import_data(
+        &mut self,
+        incoming: VaultData,
+        mode: ImportMode,
+    ) -> Result<ImportReport, VaultError> {
+        let before = self.data.clone();
+        let report = merge_data(&mut self.data, incoming, mode);
+        self.save_change(before)?;
+        self.audit(AuditEvent::Import, None);
+        Ok(report)
+    }
+}
+
+/// Encrypts `data` under `password` and writes it as a standalone vault file
+fn write_encrypted(path: &Path, password: &str, data: &VaultData) -> Result<(), VaultError> {
+    let salt = kdf::generate_salt();
+    let key = kdf::derive_key(&kdf::normalize_password(password), &salt)?;
+
+    let data_json = serde_json::to_vec(data)?;
+    let encrypted = crypto::encrypt(&key, &data_json)
+        .map_err(|e| VaultError::EncryptionError(e.to_string()))?;
+
+    let now = Utc::now();
+    let vault_file = VaultFile {
+        version: VAULT_VERSION,
+        salt,
+        kdf_params: Some(KdfParams::default()),
+        algorithm: Some(Algorithm::default()),
+        chunked: false,
+        encrypted_data: encrypted,
+        created_at: now,
+        modified_at: now,
+        lockout_policy: None,
+        failed_attempts: 0,
+        lockout_until: None,
+        wrapped_key: None,
+        recovery: None,
+        raw_password: false,
+    };
+
+    storage::save_vault_file(path, &vault_file)
+}
+
+/// Reads and decrypts a standalone vault file
+pub fn read_encrypted(path: &Path, password: &str) -> Result<VaultData, VaultError> {
+    let vault_file = migrate::migrate(storage::load_vault_file(path)?)?;
+    let (_, decrypted) = vault_file.open(password)?;
+    Ok(serde_json::from_slice(&decrypted)?)
+}
+
+/// Copies only the collections of the given types; the trash is never included
+fn select_types(data: &VaultData, types: &[SecretType]) -> VaultData {
+    fn pick<T: Clone>(items: &[T], wanted: bool) -> Vec<T> {
+        if wanted {
+            items.to_vec()
+        } else {
+            Vec::new()
+        }
+    }
+    let has = |t: SecretType| types.contains(&t);
+
+    VaultData {
+        passwords: pick(&data.passwords, has(SecretType::Password)),
+        api_keys: pick(&data.api_keys, has(SecretType::ApiKey)),
+        notes: pick(&data.notes, has(SecretType::Note)),
+        db_credentials: pick(&data.db_credentials, has(SecretType::DbCredential)),
+        tokens: pick(&data.tokens, has(SecretType::Token)),
+        totps: pick(&data.totps, has(SecretType::Totp)),
+        ssh_keys: pick(&data.ssh_keys, has(SecretType::SshKey)),
+        credit_cards: pick(&data.credit_cards, has(SecretType::CreditCard)),
+        wifi_credentials: pick(&data.wifi_credentials, has(SecretType::WifiCredential)),
+        trash: Vec::new(),
+        policy: None,
+        password_history_limit: None,
+    }
+}
+
+/// Merges every collection of `incoming` into `data`
+pub(crate) fn merge_data(
+    data: &mut VaultData,
+    incoming: VaultData,
+    mode: ImportMode,
+) -> ImportReport {
+    let mut report = ImportReport::default();
+    merge_collection(&mut data.passwords, incoming.passwords, mode, &mut report);
+    merge_collection(&mut data.api_keys, incoming.api_keys, mode, &mut report);
+    merge_collection(&mut data.notes, incoming.notes, mode, &mut report);
+    merge_collection(
+        &mut data.db_credentials,
+        incoming.db_credentials,
+        mode,
+        &mut report,
+    );
+    merge_collection(&mut data.tokens, incoming.tokens, mode, &mut report);
+    merge_collection(&mut data.totps, incoming.totps, mode, &mut report);
+    merge_collection(&mut data.ssh_keys, incoming.ssh_keys, mode, &mut report);
+    merge_collection(
+        &mut data.credit_cards,
+        incoming.credit_cards,
+        mode,
+        &mut report,
+    );
+    merge_collection(
+        &mut data.wifi_credentials,
+        incoming.wifi_credentials,
+        mode,
+        &mut report,
+    );
+    report
+}
+
+fn merge_collection<T: SecretEntry>(
+    existing: &mut Vec<T>,
+    incoming: Vec<T>,
+    mode: ImportMode,
+    report: &mut ImportReport,
+) {
+    for mut item in incoming {
+        let Some(idx) = existing.iter().position(|e| e.name() == item.name()) else {
+            ensure_unique_id(existing, &mut item);
+            existing.push(item);
+            report.added += 1;
+            continue;
+        };
+
+        match mode {
+            ImportMode::Skip => report.skipped += 1,
+            ImportMode::NewestWins if item.updated_at() <= existing[idx].updated_at() => {
+                report.skipped += 1
+            }
+            ImportMode::Replace | ImportMode::NewestWins => {
+                item.set_id(existing[idx].id().to_string());
+                existing[idx] = item;
+                report.replaced += 1;
+            }
+            ImportMode::Rename => {
+                let name = free_name(item.name(), |candidate| {
+                    existing.iter().any(|e| e.name() == candidate)
+                });
+                item.set_name(name);
+                item.set_id(Uuid::new_v4().to_string());
+                existing.push(item);
+                report.renamed += 1;
+            }
+        }
+    }
+}
+
+fn plan_collection<T: SecretEntry + Clone + PartialEq>(
+    existing: &[T],
+    incoming: &[T],
+    plan: &mut ImportPlan,
+) {
+    for item in incoming {
+        let entry = (item.secret_type(), item.name().to_string());
+        match existing.iter().find(|e| e.name() == item.name()) {
+            None => plan.new.push(entry),
+            Some(current) if same_content(current, item) => plan.duplicates.push(entry),
+            Some(_) => plan.collisions.push(entry),
+        }
+    }
+}
+
+/// Compares two secrets, ignoring ids, timestamps and usage counters
+///
+/// Secret values compare through `SecretValue`'s constant-time equality.
+pub(super) fn same_content<T: SecretEntry + Clone + PartialEq>(a: &T, b: &T) -> bool {
+    let mut b = b.clone();
+    b.set_id(a.id().to_string());
+    b.set_created_at(a.created_at());
+    b.set_updated_at(a.updated_at());
+    b.set_usage(a.last_accessed(), a.access_count());
+    *a == b
+}
+
+fn ensure_unique_id<T: SecretEntry>(existing: &[T], item: &mut T) {
+    if existing.iter().any(|e| e.id() == item.id()) {
+        item.set_id(Uuid::new_v4().to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util::{self, reopen, test_vault_at};
+
+    #[test]
+    fn test_encrypted_export_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut source = test_vault_at(dir.path().join("source.json"));
+        source
+            .add_password(Password::new(
+                "github".into(),
+                "pw".into(),
+                None,
+                None,
+                None,
+            ))
+            .unwrap();
+        source
+            .add_token(Token::new("jwt".into(), "tok".into(), None, None, None))
+            .unwrap();
+
+        let backup = dir.path().join("backup.kookie");
+        source.export_encrypted(&backup, "backup-password").unwrap();
+
+        let mut target = test_vault_at(dir.path().join("target.json"));
+        let result = target.import_encrypted(&backup, "wrong", ImportMode::Skip);
+        assert!(matches!(result, Err(VaultError::WrongPassword)));
+
+        let report = target
+            .import_encrypted(&backup, "backup-password", ImportMode::Skip)
+            .unwrap();
+        assert_eq!(report.added, 2);
+
+        let reopened = reopen(&target);
+        assert_eq!(
+            reopened
+                .get_password("github")
+                .unwrap()
+                .password
+                .expose_secret(),
+            "pw"
+        );
+        assert!(reopened.get_token("jwt").is_some());
+    }
+
+    #[test]
+    fn test_plaintext_export_requires_confirmation() {
+        let (_dir, vault) = test_util::empty_vault();
+        assert!(matches!(
+            vault.export_plaintext_json(false),
+            Err(VaultError::ExportNotConfirmed)
+        ));
+    }
+
+    #[test]
+    fn test_plaintext_roundtrip_preserves_all_collections() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut source = test_vault_at(dir.path().join("source.json"));
+        source.data = VaultData {
+            passwords: vec![Password::new(
+                "github".into(),
+                "pw".into(),
+                None,
+                None,
+                None,
+            )],
+            api_keys: vec![ApiKey::new("stripe".into(), "sk".into(), None, None)],
+            notes: vec![Note::new("codes".into(), "111".into())],
+            db_credentials: vec![DbCredential::new(
+                "prod".into(),
+                "localhost".into(),
+                None,
+                "app".into(),
+                "admin".into(),
+                "db-pw".into(),
+                None,
+                None,
+            )],
+            tokens: vec![Token::new("jwt".into(), "tok".into(), None, None, None)],
+            totps: vec![Totp::new("2fa".into(), "GEZDGNBVGY3TQOJQ".into(), None)],
+            ssh_keys: vec![SshKey::new(
+                "deploy".into(),
+                SshKeyType::Ed25519,
+                "ssh-ed25519 AAAA".into(),
+                "private".into(),
+                None,
+                None,
+            )],
+            credit_cards: vec![CreditCard::new(
+                "visa".into(),
+                "J Doe".into(),
+                "4111111111111111".into(),
+                12,
+                2099,
+                "123".into(),
+                None,
+            )],
+            wifi_credentials: vec![WifiCredential::new(
+                "home".into(),
+                "HomeNet".into(),
+                "wifi-pw".into(),
+                WifiSecurity::Wpa2,
+                false,
+            )],
+            trash: Vec::new(),
+            policy: None,
+            password_history_limit: None,
+        };
+
+        let json = source.export_plaintext_json(true).unwrap();
+        assert!(json.contains("db-pw"));
+
+        let mut target = test_vault_at(dir.path().join("target.json"));
+        let report = target
+            .import_plaintext_json(&json, ImportMode::Skip)
+            .unwrap();
+        assert_eq!(report.added, 9);
+
+        let reopened = reopen(&target);
+        assert_eq!(
+            serde_json::to_value(&reopened.data).unwrap(),
+            serde_json::to_value(&source.data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_merge_modes() {
+        let existing = Password::new("github".into(), "old".into(), None, None, None);
+        let existing_id = existing.id.clone();
+        let incoming = || VaultData {
+            passwords: vec![Password::new(
+                "github".into(),
+                "new".into(),
+                None,
+                None,
+                None,
+            )],
+            ..Default::default()
+        };
+        let base = VaultData {
+            passwords: vec![existing.clone()],
+            ..Default::default()
+        };
+
+        let mut data = base.clone();
+        let report = merge_data(&mut data, incoming(), ImportMode::Skip);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(data.passwords[0].password.expose_secret(), "old");
+
+        let mut data = base.clone();
+        let report = merge_data(&mut data, incoming(), ImportMode::Replace);
+        assert_eq!(report.replaced, 1);
+        assert_eq!(data.passwords.len(), 1);
+        assert_eq!(data.passwords[0].password.expose_secret(), "new");
+        assert_eq!(data.passwords[0].id, existing_id);
+
+        let mut data = base.clone();
+        merge_data(&mut data, incoming(), ImportMode::Rename);
+        let report = merge_data(&mut data, incoming(), ImportMode::Rename);
+        assert_eq!(report.renamed, 1);
+        let names: Vec<_> = data.passwords.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["github", "github (2)", "github (3)"]);
+    }
+
+    #[test]
+    fn test_import_assigns_fresh_id_on_id_collision() {
+        let original = Password::new("github".into(), "pw".into(), None, None, None);
+        let mut renamed_copy = original.clone();
+        renamed_copy.name = "github-old".into();
+
+        let mut data = VaultData {
+            passwords: vec![original.clone()],
+            ..Default::default()
+        };
+        let incoming = VaultData {
+            passwords: vec![renamed_copy],
+            ..Default::default()
+        };
+
+        merge_data(&mut data, incoming, ImportMode::Skip);
+        assert_eq!(data.passwords.len(), 2);
+        assert_ne!(data.passwords[0].id, data.passwords[1].id);
+    }
+
+    #[test]
+    fn test_merge_from_each_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let older = Password::new("github".into(), "old".into(), None, None, None);
+        let mut newer = Password::new("github".into(), "new".into(), None, None, None);
+        newer.updated_at = older.updated_at + chrono::Duration::minutes(5);
+
+        let other = VaultData {
+            passwords: vec![
+                newer.clone(),
+                Password::new("gitlab".into(), "pw".into(), None, None, None),
+            ],
+            notes: vec![Note::new("codes".into(), "111".into())],
+            ..Default::default()
+        };
+
+        let merged = |mode| {
+            let mut vault = test_vault_at(dir.path().join(format!("{:?}.json", mode)));
+            vault.add_password(older.clone()).unwrap();
+            let report = vault.merge_from(&other, mode);
+            (vault, report)
+        };
+        let github = |vault: &Vault| {
+            vault
+                .get_password("github")
+                .unwrap()
+                .password
+                .expose_secret()
+                .to_string()
+        };
+
+        let (vault, report) = merged(ImportMode::Skip);
+        assert_eq!(
+            report,
+            MergeReport {
+                added: 2,
+                skipped: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(github(&vault), "old");
+
+        let (vault, report) = merged(ImportMode::Replace);
+        assert_eq!(
+            report,
+            MergeReport {
+                added: 2,
+                replaced: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(github(&vault), "new");
+
+        let (vault, report) = merged(ImportMode::Rename);
+        assert_eq!(
+            report,
+            MergeReport {
+                added: 2,
+                renamed: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(github(&vault), "old");
+        assert!(vault.get_password("github (2)").is_some());
+
+        let (vault, report) = merged(ImportMode::NewestWins);
+        assert_eq!(
+            report,
+            MergeReport {
+                added: 2,
+                replaced: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(github(&vault), "new");
+        assert_eq!(vault.data.notes.len(), 1);
+    }
+
+    #[test]
+    fn test_newest_wins_keeps_more_recent_existing() {
+        let mut existing = Password::new("github".into(), "mine".into(), None, None, None);
+        let incoming = Password::new("github".into(), "theirs".into(), None, None, None);
+        existing.updated_at = incoming.updated_at + chrono::Duration::minutes(5);
+
+        let mut data = VaultData {
+            passwords: vec![existing],
+            ..Default::default()
+        };
+        let other = VaultData {
+            passwords: vec![incoming],
+            ..Default::default()
+        };
+
+        let report = merge_data(&mut data, other, ImportMode::NewestWins);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(data.passwords[0].password.expose_secret(), "mine");
+    }
+
+    #[test]
+    fn test_plan_import_classifies_without_writing() {
+        let (_dir, mut vault) = test_util::test_vault();
+        let github = Password::new("github".into(), "pw".into(), None, None, None);
+        vault.add_password(github.clone()).unwrap();
+        vault
+            .add_note(Note::new("codes".into(), "111".into()))
+            .unwrap();
+        let on_disk = std::fs::read(&vault.path).unwrap();
+
+        // A copy made elsewhere: new id and timestamps, same contents
+        let mut copy = Password::new("github".into(), "pw".into(), None, None, None);
+        copy.access_count = 4;
+        let incoming = VaultData {
+            passwords: vec![copy],
+            notes: vec![Note::new("codes".into(), "222".into())],
+            tokens: vec![Token::new("jwt".into(), "tok".into(), None, None, None)],
+            ..Default::default()
+        };
+
+        let plan = vault.plan_import(&incoming);
+        assert_eq!(plan.new, vec![(SecretType::Token, "jwt".to_string())]);
+        assert_eq!(
+            plan.collisions,
+            vec![(SecretType::Note, "codes".to_string())]
+        );
+        assert_eq!(
+            plan.duplicates,
+            vec![(SecretType::Password, "github".to_string())]
+        );
+
+        assert_eq!(std::fs::read(&vault.path).unwrap(), on_disk);
+        assert_eq!(vault.data.notes[0].content.expose_secret(), "111");
+        assert!(vault.get_token("jwt").is_none());
+    }
+
+    #[test]
+    fn test_selective_export_only_includes_chosen_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut source = test_vault_at(dir.path().join("source.json"));
+        source
+            .add_password(Password::new(
+                "github".into(),
+                "pw".into(),
+                None,
+                None,
+                None,
+            ))
+            .unwrap();
+        source
+            .add_note(Note::new("diary".into(), "private".into()))
+            .unwrap();
+        source
+            .add_token(Token::new("ci".into(), "tok1".into(), None, None, None))
+            .unwrap();
+        source
+            .add_token(Token::new("deploy".into(), "tok2".into(), None, None, None))
+            .unwrap();
+        source.delete_token("deploy").unwrap();
+
+        let backup = dir.path().join("tokens.kookie");
+        source
+            .export_encrypted_selective(&backup, "share-password", &[SecretType::Token])
+            .unwrap();
+
+        let mut target = test_vault_at(dir.path().join("target.json"));
+        let report = target
+            .import_encrypted(&backup, "share-password", ImportMode::Skip)
+            .unwrap();
+        assert_eq!(report.added, 1);
+
+        let entries = target.data.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].secret_type(), SecretType::Token);
+        assert_eq!(entries[0].name(), "ci");
+        assert!(target.data.trash.is_empty());
+    }
+}
@@ -0,0 +1,355 @@
+//! Soft-deleted secrets
+//!
+//! `delete_*` moves a secret into `VaultData::trash` instead of dropping it,
+//! so a mistaken delete can be undone with `Vault::restore`. Trashed secrets
+//! are not part of any collection and so never show up in `list_*`, `get_*`
+//! or search. Entries older than `TRASH_RETENTION_DAYS` are purged whenever
+//! something new is trashed.
+
+use super::types::*;
+use super::{AuditEvent, Vault, VaultData, VaultError};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How long deleted secrets are kept before being purged automatically
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// A deleted secret of any type
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", content = "secret", rename_all = "snake_case")]
+pub enum TrashedItem {
+    Password(Password),
+    ApiKey(ApiKey),
+    Note(Note),
+    DbCredential(DbCredential),
+    Token(Token),
+    Totp(Totp),
+    SshKey(SshKey),
+    CreditCard(CreditCard),
+    WifiCredential(WifiCredential),
+}
+
+impl TrashedItem {
+    /// Returns the common accessors of the wrapped secret
+    pub fn entry(&self) -> &dyn SecretEntry {
+        match self {
+            TrashedItem::Password(s) => s,
+            TrashedItem::ApiKey(s) => s,
+            TrashedItem::Note(s) => s,
+            TrashedItem::DbCredential(s) => s,
+            TrashedItem::Token(s) => s,
+            TrashedItem::Totp(s) => s,
+            TrashedItem::SshKey(s) => s,
+            TrashedItem::CreditCard(s) => s,
+            TrashedItem::WifiCredential(s) => s,
+        }
+    }
+}
+
+/// A secret in the trash and when it was deleted
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TrashedSecret {
+    pub deleted_at: DateTime<Utc>,
+    pub item: TrashedItem,
+}
+
+impl Vault {
+    /// Returns the secrets currently in the trash
+    pub fn list_trash(&self) -> &[TrashedSecret] {
+        &self.data.trash
+    }
+
+    /// Moves a secret out of the trash and back into its collection
+    ///
+    /// Fails with `DuplicateName` if a secret of the same type with the same
+    /// name has been added since.
+    pub fn restore(&mut self, id: &str) -> Result<(), VaultError> {
+        let before = self.data.clone();
+        let idx = self
+            .data
+            .trash
+            .iter()
+            .position(|t| t.item.entry().id() == id)
+            .ok_or_else(|| VaultError::SecretNotFound(id.to_string()))?;
+
+        let entry = self.data.trash[idx].item.entry();
+        let (secret_type, name) = (entry.secret_type(), entry.name().to_string());
+        let taken = self
+            .data
+            .entries()
+            .into_iter()
+            .any(|e| e.secret_type() == secret_type && e.name() == name);
+        if taken {
+            return Err(VaultError::DuplicateName(name));
+        }
+
+        let trashed = self.data.trash.remove(idx);
+        restore_item(&mut self.data, trashed.item);
+        self.save_change(before)
+    }
+
+    /// Permanently deletes everything in the trash, returning how many secrets were removed
+    pub fn empty_trash(&mut self) -> Result<usize, VaultError> {
+        let before = self.data.clone();
+        let count = self.data.trash.len();
+        self.data.trash.clear();
+        self.save_change(before)?;
+        Ok(count)
+    }
+
+    /// Permanently deletes trashed secrets deleted more than `age` ago
+    pub fn purge_older_than(&mut self, age: Duration) -> Result<usize, VaultError> {
+        let before = self.data.clone();
+        let count = purge(&mut self.data, age);
+        self.save_change(before)?;
+        Ok(count)
+    }
+
+    /// Moves every secret `pred` accepts (given its type and name) to the
+    /// trash with a single save, returning how many were deleted
+    pub fn delete_where(
+        &mut self,
+        pred: impl Fn(SecretType, &str) -> bool,
+    ) -> Result<usize, VaultError> {
+        let before = self.data.clone();
+        fn take<T: SecretEntry>(
+            items: &mut Vec<T>,
+            pred: &impl Fn(SecretType, &str) -> bool,
+        ) -> Vec<T> {
+            let (taken, kept) = std::mem::take(items)
+                .into_iter()
+                .partition(|s| pred(s.secret_type(), s.name()));
+            *items = kept;
+            taken
+        }
+
+        let data = &mut self.data;
+        let mut removed: Vec<TrashedItem> = Vec::new();
+        removed.extend(
+            take(&mut data.passwords, &pred)
+                .into_iter()
+                .map(TrashedItem::Password),
+        );
+        removed.extend(
+            take(&mut data.api_keys, &pred)
+                .into_iter()
+                .map(TrashedItem::ApiKey),
+        );
+        removed.extend(
+            take(&mut data.notes, &pred)
+                .into_iter()
+                .map(TrashedItem::Note),
+        );
+        removed.extend(
+            take(&mut data.db_credentials, &pred)
+                .into_iter()
+                .map(TrashedItem::DbCredential),
+        );
+        removed.extend(
+            take(&mut data.tokens, &pred)
+                .into_iter()
+                .map(TrashedItem::Token),
+        );
+        removed.extend(
+            take(&mut data.totps, &pred)
+                .into_iter()
+                .map(TrashedItem::Totp),
+        );
+        removed.extend(
+            take(&mut data.ssh_keys, &pred)
+                .into_iter()
+                .map(TrashedItem::SshKey),
+        );
+        removed.extend(
+            take(&mut data.credit_cards, &pred)
+                .into_iter()
+                .map(TrashedItem::CreditCard),
+        );
+        removed.extend(
+            take(&mut data.wifi_credentials, &pred)
+                .into_iter()
+                .map(TrashedItem::WifiCredential),
+        );
+
+        let count = removed.len();
+        if count == 0 {
+            return Ok(0);
+        }
+        let names: Vec<(SecretType, String)> = removed
+            .iter()
+            .map(|item| (item.entry().secret_type(), item.entry().name().to_string()))
+            .collect();
+        for item in removed {
+            self.move_to_trash(item);
+        }
+        self.save_change(before)?;
+        for (secret_type, name) in &names {
+            self.audit(AuditEvent::Delete, Some((*secret_type, name)));
+        }
+        Ok(count)
+    }
+
+    /// Moves every secret carrying `tag` to the trash (see `delete_where`)
+    pub fn delete_by_tag(&mut self, tag: &str) -> Result<usize, VaultError> {
+        let tagged = self.find_by_tag(tag);
+        self.delete_where(|secret_type, name| {
+            tagged.iter().any(|(t, n)| *t == secret_type && n == name)
+        })
+    }
+
+    /// Puts a just-deleted secret into the trash and drops expired entries (doesn't save)
+    pub(super) fn move_to_trash(&mut self, item: TrashedItem) {
+        purge(&mut self.data, Duration::days(TRASH_RETENTION_DAYS));
+        self.data.trash.push(TrashedSecret {
+            deleted_at: Utc::now(),
+            item,
+        });
+    }
+}
+
+pub(super) fn purge(data: &mut VaultData, age: Duration) -> usize {
+    let cutoff = Utc::now() - age;
+    let before = data.trash.len();
+    data.trash.retain(|t| t.deleted_at >= cutoff);
+    before - data.trash.len()
+}
+
+fn restore_item(data: &mut VaultData, item: TrashedItem) {
+    match item {
+        TrashedItem::Password(s) => data.passwords.push(s),
+        TrashedItem::ApiKey(s) => data.api_keys.push(s),
+        TrashedItem::Note(s) => data.notes.push(s),
+        TrashedItem::DbCredential(s) => data.db_credentials.push(s),
+        TrashedItem::Token(s) => data.tokens.push(s),
+        TrashedItem::Totp(s) => data.totps.push(s),
+        TrashedItem::SshKey(s) => data.ssh_keys.push(s),
+        TrashedItem::CreditCard(s) => data.credit_cards.push(s),
+        TrashedItem::WifiCredential(s) => data.wifi_credentials.push(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util::test_vault;
+
+    #[test]
+    fn test_delete_and_restore_roundtrip() {
+        let (_dir, mut vault) = test_vault();
+        vault
+            .add_api_key(ApiKey::new("stripe".into(), "sk_1".into(), None, None))
+            .unwrap();
+        let id = vault.get_api_key("stripe").unwrap().id.clone();
+
+        vault.delete_api_key("stripe").unwrap();
+        assert!(vault.list_api_keys().is_empty());
+        assert!(vault.search("stripe").is_empty());
+        assert_eq!(vault.list_trash().len(), 1);
+
+        vault.restore(&id).unwrap();
+        assert!(vault.list_trash().is_empty());
+        let restored = vault.get_api_key("stripe").unwrap();
+        assert_eq!(restored.id, id);
+        assert_eq!(restored.key.expose_secret(), "sk_1");
+
+        assert!(matches!(
+            vault.restore(&id),
+            Err(VaultError::SecretNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_restore_refuses_name_collision() {
+        let (_dir, mut vault) = test_vault();
+        vault
+            .add_note(Note::new("codes".into(), "old".into()))
+            .unwrap();
+        let id = vault.delete_note("codes").unwrap().id;
+        vault
+            .add_note(Note::new("codes".into(), "new".into()))
+            .unwrap();
+
+        assert!(matches!(
+            vault.restore(&id),
+            Err(VaultError::DuplicateName(name)) if name == "codes"
+        ));
+        assert_eq!(vault.list_trash().len(), 1);
+    }
+
+    #[test]
+    fn test_old_trash_is_purged() {
+        let (_dir, mut vault) = test_vault();
+        vault
+            .add_token(Token::new("a".into(), "t".into(), None, None, None))
+            .unwrap();
+        vault
+            .add_token(Token::new("b".into(), "t".into(), None, None, None))
+            .unwrap();
+        vault.delete_token("a").unwrap();
+        vault.data.trash[0].deleted_at = Utc::now() - Duration::days(TRASH_RETENTION_DAYS + 1);
+
+        // Trashing something else drops entries past the retention period
+        vault.delete_token("b").unwrap();
+        let names: Vec<_> = vault
+            .list_trash()
+            .iter()
+            .map(|t| t.item.entry().name().to_string())
+            .collect();
+        assert_eq!(names, vec!["b"]);
+
+        vault.data.trash[0].deleted_at = Utc::now() - Duration::days(2);
+        assert_eq!(vault.purge_older_than(Duration::days(3)).unwrap(), 0);
+        assert_eq!(vault.purge_older_than(Duration::days(1)).unwrap(), 1);
+        assert!(vault.list_trash().is_empty());
+    }
+
+    #[test]
+    fn test_empty_trash() {
+        let (_dir, mut vault) = test_vault();
+        vault.add_note(Note::new("a".into(), "1".into())).unwrap();
+        vault.delete_note("a").unwrap();
+
+        assert_eq!(vault.empty_trash().unwrap(), 1);
+        assert!(vault.list_trash().is_empty());
+    }
+
+    #[test]
+    fn test_delete_by_tag_single_save() {
+        let (_dir, mut vault) = test_vault();
+        vault
+            .add_api_key(ApiKey::new("stripe".into(), "sk_1".into(), None, None))
+            .unwrap();
+        vault
+            .add_note(Note::new("setup".into(), "steps".into()))
+            .unwrap();
+        vault
+            .add_note(Note::new("keep".into(), "other".into()))
+            .unwrap();
+        vault.add_tag(SecretType::ApiKey, "stripe", "shop").unwrap();
+        vault.add_tag(SecretType::Note, "setup", "shop").unwrap();
+
+        let saves_before = vault.save_count.get();
+        assert_eq!(vault.delete_by_tag("shop").unwrap(), 2);
+        assert_eq!(vault.save_count.get() - saves_before, 1);
+
+        assert!(vault.get_api_key("stripe").is_none());
+        assert!(vault.get_note("setup").is_none());
+        assert!(vault.get_note("keep").is_some());
+        assert_eq!(vault.list_trash().len(), 2);
+    }
+
+    #[test]
+    fn test_delete_where_without_matches_does_not_save() {
+        let (_dir, mut vault) = test_vault();
+        vault
+            .add_note(Note::new("keep".into(), "other".into()))
+            .unwrap();
+
+        let saves_before = vault.save_count.get();
+        let deleted = vault
+            .delete_where(|t, name| t == SecretType::Password && name.starts_with("tmp-"))
+            .unwrap();
+        assert_eq!(deleted, 0);
+        assert_eq!(vault.save_count.get(), saves_before);
+    }
+}
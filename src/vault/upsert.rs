@@ -0,0 +1,214 @@
+//! Idempotent add: insert a secret or replace the one with the same name
+//!
+//! Handy for import and sync scripts that run repeatedly. A replaced secret
+//! keeps its id and creation time and gets a fresh `updated_at`; everything
+//! else comes from the incoming secret. Replaced password values go into the
+//! password history as with `update_password`.
+
+use super::types::*;
+use super::{AuditEvent, Vault, VaultData, VaultError};
+use chrono::Utc;
+
+/// What an upsert did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertResult {
+    Created,
+    Updated,
+}
+
+/// Replaces the same-named entry or appends `item`, returning the replaced entry
+fn replace_or_push<T: SecretEntry>(items: &mut Vec<T>, mut item: T) -> Option<T> {
+    match items.iter().position(|s| s.name() == item.name()) {
+        Some(idx) => {
+            item.set_id(items[idx].id().to_string());
+            item.set_created_at(items[idx].created_at());
+            item.set_updated_at(Utc::now());
+            Some(std::mem::replace(&mut items[idx], item))
+        }
+        None => {
+            items.push(item);
+            None
+        }
+    }
+}
+
+impl Vault {
+    /// Inserts the password or replaces the one with the same name
+    ///
+    /// The vault's password policy applies as for `add_password`.
+    pub fn upsert_password(&mut self, password: Password) -> Result<UpsertResult, VaultError> {
+        let before = self.data.clone();
+        if let Some(policy) = &self.data.policy {
+            policy.check(password.password.expose_secret())?;
+        }
+        let name = password.name.clone();
+        let replaced = replace_or_push(&mut self.data.passwords, password);
+        let updated = replaced.is_some();
+        if let Some(old) = replaced {
+            let limit = self.password_history_limit();
+            let current = self
+                .data
+                .passwords
+                .iter_mut()
+                .find(|p| p.name == name)
+                .expect("just replaced");
+            let mut history = old.history;
+            if old.password != current.password {
+                history.push(PasswordHistoryEntry {
+                    password: old.password,
+                    replaced_at: current.updated_at,
+                });
+            }
+            current.history = history;
+            current.trim_history(limit);
+        }
+        self.finish_upsert(before, SecretType::Password, &name, updated)
+    }
+
+    /// Inserts the API key or replaces the one with the same name
+    pub fn upsert_api_key(&mut self, api_key: ApiKey) -> Result<UpsertResult, VaultError> {
+        let before = self.data.clone();
+        let name = api_key.name.clone();
+        let replaced = replace_or_push(&mut self.data.api_keys, api_key).is_some();
+        self.finish_upsert(before, SecretType::ApiKey, &name, replaced)
+    }
+
+    /// Inserts the note or replaces the one with the same name
+    pub fn upsert_note(&mut self, note: Note) -> Result<UpsertResult, VaultError> {
+        let before = self.data.clone();
+        let name = note.name.clone();
+        let replaced = replace_or_push(&mut self.data.notes, note).is_some();
+        self.finish_upsert(before, SecretType::Note, &name, replaced)
+    }
+
+    /// Inserts the credential or replaces the one with the same name
+    pub fn upsert_db_credential(
+        &mut self,
+        credential: DbCredential,
+    ) -> Result<UpsertResult, VaultError> {
+        let before = self.data.clone();
+        let name = credential.name.clone();
+        let replaced = replace_or_push(&mut self.data.db_credentials, credential).is_some();
+        self.finish_upsert(before, SecretType::DbCredential, &name, replaced)
+    }
+
+    /// Inserts the token or replaces the one with the same name
+    pub fn upsert_token(&mut self, token: Token) -> Result<UpsertResult, VaultError> {
+        let before = self.data.clone();
+        let name = token.name.clone();
+        let replaced = replace_or_push(&mut self.data.tokens, token).is_some();
+        self.finish_upsert(before, SecretType::Token, &name, replaced)
+    }
+
+    /// Inserts the TOTP secret or replaces the one with the same name
+    pub fn upsert_totp(&mut self, totp: Totp) -> Result<UpsertResult, VaultError> {
+        let before = self.data.clone();
+        let name = totp.name.clone();
+        let replaced = replace_or_push(&mut self.data.totps, totp).is_some();
+        self.finish_upsert(before, SecretType::Totp, &name, replaced)
+    }
+
+    /// Inserts the SSH key or replaces the one with the same name
+    pub fn upsert_ssh_key(&mut self, ssh_key: SshKey) -> Result<UpsertResult, VaultError> {
+        let before = self.data.clone();
+        let name = ssh_key.name.clone();
+        let replaced = replace_or_push(&mut self.data.ssh_keys, ssh_key).is_some();
+        self.finish_upsert(before, SecretType::SshKey, &name, replaced)
+    }
+
+    /// Inserts the card or replaces the one with the same name
+    pub fn upsert_credit_card(&mut self, card: CreditCard) -> Result<UpsertResult, VaultError> {
+        let before = self.data.clone();
+        let name = card.name.clone();
+        let replaced = replace_or_push(&mut self.data.credit_cards, card).is_some();
+        self.finish_upsert(before, SecretType::CreditCard, &name, replaced)
+    }
+
+    /// Inserts the WiFi credential or replaces the one with the same name
+    pub fn upsert_wifi_credential(
+        &mut self,
+        wifi: WifiCredential,
+    ) -> Result<UpsertResult, VaultError> {
+        let before = self.data.clone();
+        let name = wifi.name.clone();
+        let replaced = replace_or_push(&mut self.data.wifi_credentials, wifi).is_some();
+        self.finish_upsert(before, SecretType::WifiCredential, &name, replaced)
+    }
+
+    fn finish_upsert(
+        &mut self,
+        before: VaultData,
+        secret_type: SecretType,
+        name: &str,
+        replaced: bool,
+    ) -> Result<UpsertResult, VaultError> {
+        self.save_change(before)?;
+        if replaced {
+            self.audit(AuditEvent::Update, Some((secret_type, name)));
+            Ok(UpsertResult::Updated)
+        } else {
+            self.audit(AuditEvent::Add, Some((secret_type, name)));
+            Ok(UpsertResult::Created)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vault::test_util::test_vault;
+    use chrono::Duration;
+
+    #[test]
+    fn test_upsert_inserts_new_secret() {
+        let (_dir, mut vault) = test_vault();
+        let key = ApiKey::new("stripe".into(), "sk_1".into(), None, None);
+        let id = key.id.clone();
+
+        assert_eq!(vault.upsert_api_key(key).unwrap(), UpsertResult::Created);
+        assert_eq!(vault.get_api_key("stripe").unwrap().id, id);
+    }
+
+    #[test]
+    fn test_upsert_updates_and_keeps_identity() {
+        let (_dir, mut vault) = test_vault();
+        let mut original = ApiKey::new("stripe".into(), "sk_1".into(), None, None);
+        original.created_at = Utc::now() - Duration::days(10);
+        original.updated_at = original.created_at;
+        let (id, created_at) = (original.id.clone(), original.created_at);
+        vault.add_api_key(original).unwrap();
+
+        let incoming = ApiKey::new("stripe".into(), "sk_2".into(), None, Some("Stripe".into()));
+        assert_eq!(
+            vault.upsert_api_key(incoming).unwrap(),
+            UpsertResult::Updated
+        );
+
+        let stored = vault.get_api_key("stripe").unwrap();
+        assert_eq!(vault.list_api_keys().len(), 1);
+        assert_eq!(stored.id, id);
+        assert_eq!(stored.created_at, created_at);
+        assert!(stored.updated_at > created_at);
+        assert_eq!(stored.key.expose_secret(), "sk_2");
+        assert_eq!(stored.service.as_deref(), Some("Stripe"));
+    }
+
+    #[test]
+    fn test_upsert_password_records_history() {
+        let (_dir, mut vault) = test_vault();
+        let first = Password::new("github".into(), "old".into(), None, None, None);
+        assert_eq!(vault.upsert_password(first).unwrap(), UpsertResult::Created);
+
+        let second = Password::new("github".into(), "new".into(), None, None, None);
+        assert_eq!(
+            vault.upsert_password(second).unwrap(),
+            UpsertResult::Updated
+        );
+        let same = Password::new("github".into(), "new".into(), None, None, None);
+        assert_eq!(vault.upsert_password(same).unwrap(), UpsertResult::Updated);
+
+        let history = vault.password_history("github").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].password.expose_secret(), "old");
+    }
+}